@@ -2,7 +2,7 @@ use std::convert::TryInto;
 
 use crate::util::{number::u24, traits::RawSize};
 
-use super::flac_metadata_block_data::{FlacMetadataBlockData, VorbisCommentBlock, PictureBlock};
+use super::flac_metadata_block_data::{CueSheetBlock, FlacMetadataBlockData, VorbisCommentBlock, PictureBlock};
 
 const LAST_BLOCK_FLAG : u8 = 0b10_000_000;
 const BLOCK_TYPE_FLAG : u8 = !LAST_BLOCK_FLAG;
@@ -91,6 +91,14 @@ impl FlacMetadataBlock {
             data: FlacMetadataBlockData::PICTURE(picture_block),
         }
     }
+    pub (crate) fn new_cue_sheet_block(cue_sheet_block : CueSheetBlock) -> Self {
+        Self {
+            is_last_block: false,
+            block_type: FlacMetadataBlockType::CUESHEET,
+            metadata_len: (cue_sheet_block.raw_size() as u32).into(),
+            data: FlacMetadataBlockData::CUESHEET(cue_sheet_block),
+        }
+    }
     pub (crate) fn update_size(&mut self){ 
         self.metadata_len = (self.data.raw_size() as u32).into()
     }
@@ -113,4 +121,8 @@ impl FlacMetadataBlock {
     pub (crate) fn as_picture_block(&self) -> Option<&PictureBlock> {
         self.data.as_picture_block()
     }
+
+    pub (crate) fn as_cue_sheet_block(&self) -> Option<&CueSheetBlock> {
+        self.data.as_cue_sheet_block()
+    }
 }
@@ -2,12 +2,17 @@ use crate::{
     id3::code::picture_code::picture_type::PictureType,
     util::{traits::{StringConvert, ToU16, ToU32}, vorbis_vector::VorbisVector},
     util::{number::u24, traits::RawSize}, PictureFormat,
+    util::cd_toc::{CdToc, CdTocTrack, LEAD_OUT_TRACK, SAMPLES_PER_SECTOR},
 };
 use std::{convert::TryInto, fmt::Display, str::FromStr};
 
 use super::flac_metadata_block::FlacMetadataBlockType;
 
 const COMPACT_DISC_FLAG: u8 = 0b10_000_000;
+/// Length of the media catalog number field
+const MEDIA_CATALOG_NUMBER_SIZE: usize = 128;
+/// Lead-in of a CD : 2 seconds at 44.1 kHz
+const CD_LEAD_IN_SAMPLES: u64 = 88_200;
 
 const TRACK_TYPE_MASK: u8 = 0b10_000_000;
 const PRE_EMPHASIS_MASK: u8 = 0b01_000_000;
@@ -128,6 +133,8 @@ impl CueSheetTrackIndex {
     pub(crate) fn new(buffer: &mut Vec<u8>) -> Option<Self> {
         let offset = u64::from_be_bytes(buffer.drain(0..8).collect::<Vec<u8>>().try_into().ok()?);
         let index_point_number = buffer.remove(0);
+        // Reserved
+        buffer.drain(0..3);
         Some(Self {
             offset,
             index_point_number,
@@ -211,12 +218,15 @@ pub(crate) struct CueSheetBlock {
 
 impl RawSize for CueSheetBlock {
     fn raw_size(&self) -> usize {
-        128 + 8 + 1 + 258 + 1 + self.cuesheets_tracks.len()
+        MEDIA_CATALOG_NUMBER_SIZE + 8 + 1 + 258 + 1
+            + self.cuesheets_tracks.iter().map(|cst| cst.raw_size()).sum::<usize>()
     }
 
     fn raw_bytes(&self) -> Vec<u8> {
         let mut bytes = vec![];
-        bytes.append(&mut self.media_catalog_number.clone().into_bytes());
+        let mut media_catalog_number = self.media_catalog_number.clone().into_bytes();
+        media_catalog_number.resize(MEDIA_CATALOG_NUMBER_SIZE, 0);
+        bytes.append(&mut media_catalog_number);
         bytes.append(&mut self.number_lead_sample.to_be_bytes().to_vec());
         if self.is_compact_disc {
             bytes.push(COMPACT_DISC_FLAG)
@@ -232,6 +242,76 @@ impl RawSize for CueSheetBlock {
     }
 }
 
+impl CueSheetBlock {
+    /// Create the cue sheet of a CD from its table of contents, the offsets of the tracks being their index 01
+    pub(crate) fn from_cd_toc(cd_toc: &CdToc) -> Self {
+        let mut cuesheets_tracks = cd_toc
+            .tracks
+            .iter()
+            .map(|track| CueSheetTrack {
+                track_offset: track.offset as u64 * SAMPLES_PER_SECTOR,
+                track_number: track.number,
+                track_isrc: vec![0u8; 12],
+                is_audio: track.is_audio,
+                is_pre_emphasis: false,
+                tracks_index: vec![CueSheetTrackIndex {
+                    offset: 0,
+                    index_point_number: 1,
+                }],
+            })
+            .collect::<Vec<CueSheetTrack>>();
+        cuesheets_tracks.push(CueSheetTrack {
+            track_offset: cd_toc.lead_out as u64 * SAMPLES_PER_SECTOR,
+            track_number: LEAD_OUT_TRACK,
+            track_isrc: vec![0u8; 12],
+            is_audio: true,
+            is_pre_emphasis: false,
+            tracks_index: vec![],
+        });
+        Self {
+            media_catalog_number: String::new(),
+            number_lead_sample: CD_LEAD_IN_SAMPLES,
+            is_compact_disc: true,
+            cuesheets_tracks,
+        }
+    }
+
+    /// Returns the table of contents of the CD, a track starting at its index 01
+    ///
+    /// `None` if the cue sheet doesn't come from a CD or has no lead-out
+    pub(crate) fn to_cd_toc(&self) -> Option<CdToc> {
+        if !self.is_compact_disc {
+            return None;
+        }
+        let lead_out = self
+            .cuesheets_tracks
+            .iter()
+            .find(|track| track.track_number == LEAD_OUT_TRACK)?;
+        let tracks = self
+            .cuesheets_tracks
+            .iter()
+            .filter(|track| track.track_number != LEAD_OUT_TRACK)
+            .map(|track| {
+                let index = track
+                    .tracks_index
+                    .iter()
+                    .find(|index| index.index_point_number == 1)
+                    .or(track.tracks_index.first())
+                    .map_or(0, |index| index.offset);
+                CdTocTrack {
+                    number: track.track_number,
+                    offset: ((track.track_offset + index) / SAMPLES_PER_SECTOR) as u32,
+                    is_audio: track.is_audio,
+                }
+            })
+            .collect();
+        Some(CdToc {
+            tracks,
+            lead_out: (lead_out.track_offset / SAMPLES_PER_SECTOR) as u32,
+        })
+    }
+}
+
 pub(crate) struct StreamInfoBlock {
     min_block_size: u16,
     max_block_size: u16,
@@ -861,4 +941,11 @@ impl FlacMetadataBlockData {
             _ => None,
         }
     }
+
+    pub(crate) fn as_cue_sheet_block(&self) -> Option<&CueSheetBlock> {
+        match self {
+            Self::CUESHEET(cs) => Some(cs),
+            _ => None,
+        }
+    }
 }
@@ -2,13 +2,14 @@ use std::io::{Error, Write};
 use std::{fs::OpenOptions, io::Read};
 
 use crate::PictureFormat;
+use crate::util::cd_toc::CdToc;
 use crate::id3::code::picture_code::picture_type::PictureType;
 use crate::util::traits::{RawSize, StringConvert};
 
 use super::flac_metadata_block::{FlacMetadataBlock, FlacMetadataBlockType};
 
 use super::flac_metadata_block::FlacMetadataBlockType::*;
-use super::flac_metadata_block_data::PictureBlock;
+use super::flac_metadata_block_data::{CueSheetBlock, PictureBlock};
 
 pub(crate) const FLAC_ID: &'static str = "fLaC";
 
@@ -1398,3 +1399,30 @@ impl FlacTag {
         });
     }
 }
+
+impl FlacTag {
+    /// Returns the table of contents of the CD stored in the CUESHEET block
+    ///
+    /// `None` if there is no cue sheet or if it doesn't come from a CD
+    pub fn cd_toc(&self) -> Option<CdToc> {
+        self.metadata_blocks
+            .iter()
+            .find_map(|block| block.as_cue_sheet_block())
+            .and_then(|cue_sheet| cue_sheet.to_cd_toc())
+    }
+    /// Replace the CUESHEET block by the cue sheet of the CD `cd_toc`
+    ///
+    /// The tracks start at their index 01, the sample offsets being the sector offsets at 44.1 kHz
+    pub fn set_cd_toc(&mut self, cd_toc: &CdToc) {
+        self.remove_cd_toc();
+        let block = FlacMetadataBlock::new_cue_sheet_block(CueSheetBlock::from_cd_toc(cd_toc));
+        self.insert_metadata_block(block)
+    }
+    /// Remove the CUESHEET block
+    pub fn remove_cd_toc(&mut self) {
+        self.metadata_blocks.retain(|block| block.block_type() != &CUESHEET);
+        if let Some(last_block) = self.metadata_blocks.last_mut() {
+            last_block.set_last(true)
+        }
+    }
+}
@@ -0,0 +1,123 @@
+/// Event of the event timing codes (ETCO)
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EventType {
+    Padding,
+    EndOfInitialSilence,
+    IntroStart,
+    MainPartStart,
+    OutroStart,
+    OutroEnd,
+    VerseStart,
+    RefrainStart,
+    InterludeStart,
+    ThemeStart,
+    VariationStart,
+    KeyChange,
+    TimeChange,
+    MomentaryUnwantedNoise,
+    SustainedNoise,
+    SustainedNoiseEnd,
+    IntroEnd,
+    MainPartEnd,
+    VerseEnd,
+    RefrainEnd,
+    ThemeEnd,
+    /// ID3v2.4 only
+    Profanity,
+    /// ID3v2.4 only
+    ProfanityEnd,
+    /// Synchronisation not predefined by the specification, from 0 to 15
+    NotPredefinedSync(u8),
+    /// Start of the silence
+    AudioEnd,
+    AudioFileEnds,
+    /// Event described by the bytes following $FF
+    Extended(Vec<u8>),
+    /// Code reserved for future use
+    Reserved(u8)
+}
+
+impl EventType {
+    /// Parse the event at the beginning of `bytes` and remove it from the buffer
+    pub(crate) fn from_bytes(bytes : &mut Vec<u8>) -> Option<Self> {
+        let event = match *bytes.first()? {
+            0xFF => {
+                // All the following $FF have the same meaning, the event ends at the first other byte
+                let end = bytes.iter().skip(1).position(|byte| *byte != 0xFF)? + 1;
+                Self::Extended(bytes.drain(0..=end).skip(1).collect())
+            }
+            code => {
+                bytes.remove(0);
+                Self::from_raw_value(code)
+            }
+        };
+        Some(event)
+    }
+
+    fn from_raw_value(value : u8) -> Self {
+        match value {
+            0x00 => Self::Padding,
+            0x01 => Self::EndOfInitialSilence,
+            0x02 => Self::IntroStart,
+            0x03 => Self::MainPartStart,
+            0x04 => Self::OutroStart,
+            0x05 => Self::OutroEnd,
+            0x06 => Self::VerseStart,
+            0x07 => Self::RefrainStart,
+            0x08 => Self::InterludeStart,
+            0x09 => Self::ThemeStart,
+            0x0A => Self::VariationStart,
+            0x0B => Self::KeyChange,
+            0x0C => Self::TimeChange,
+            0x0D => Self::MomentaryUnwantedNoise,
+            0x0E => Self::SustainedNoise,
+            0x0F => Self::SustainedNoiseEnd,
+            0x10 => Self::IntroEnd,
+            0x11 => Self::MainPartEnd,
+            0x12 => Self::VerseEnd,
+            0x13 => Self::RefrainEnd,
+            0x14 => Self::ThemeEnd,
+            0x15 => Self::Profanity,
+            0x16 => Self::ProfanityEnd,
+            0xE0..=0xEF => Self::NotPredefinedSync(value - 0xE0),
+            0xFD => Self::AudioEnd,
+            0xFE => Self::AudioFileEnds,
+            _ => Self::Reserved(value)
+        }
+    }
+
+    /// Returns the raw bytes of the event
+    pub(crate) fn as_bytes(&self) -> Vec<u8> {
+        let code = match self {
+            Self::Padding => 0x00,
+            Self::EndOfInitialSilence => 0x01,
+            Self::IntroStart => 0x02,
+            Self::MainPartStart => 0x03,
+            Self::OutroStart => 0x04,
+            Self::OutroEnd => 0x05,
+            Self::VerseStart => 0x06,
+            Self::RefrainStart => 0x07,
+            Self::InterludeStart => 0x08,
+            Self::ThemeStart => 0x09,
+            Self::VariationStart => 0x0A,
+            Self::KeyChange => 0x0B,
+            Self::TimeChange => 0x0C,
+            Self::MomentaryUnwantedNoise => 0x0D,
+            Self::SustainedNoise => 0x0E,
+            Self::SustainedNoiseEnd => 0x0F,
+            Self::IntroEnd => 0x10,
+            Self::MainPartEnd => 0x11,
+            Self::VerseEnd => 0x12,
+            Self::RefrainEnd => 0x13,
+            Self::ThemeEnd => 0x14,
+            Self::Profanity => 0x15,
+            Self::ProfanityEnd => 0x16,
+            Self::NotPredefinedSync(sync) => 0xE0 | (sync & 0x0F),
+            Self::AudioEnd => 0xFD,
+            Self::AudioFileEnds => 0xFE,
+            Self::Extended(bytes) => return [0xFF].iter().chain(bytes.iter()).copied().collect(),
+            Self::Reserved(code) => *code
+        };
+        vec![code]
+    }
+}
@@ -1 +1,2 @@
-pub (crate) mod time_stamp_format;
\ No newline at end of file
+pub (crate) mod time_stamp_format;
+pub (crate) mod event_type;
\ No newline at end of file
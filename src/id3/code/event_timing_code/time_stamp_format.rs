@@ -1,8 +1,10 @@
-
+/// Unit of the timestamps of the synchronised frames
 #[repr(u8)]
-#[derive(Clone, Copy)]
-pub(crate) enum TimeStampFormat {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimeStampFormat {
+    /// Absolute time, using MPEG frames as unit
     MPEGFrames = 0x01,
+    /// Absolute time, using milliseconds as unit
     Milliseconds = 0x02
 }
 
@@ -14,4 +16,4 @@ impl TimeStampFormat {
             _ => None
         }
     }
-}
\ No newline at end of file
+}
@@ -1,3 +1,6 @@
 pub (crate) mod event_timing_code;
 pub (crate) mod text_code;
-pub (crate) mod picture_code;
\ No newline at end of file
+pub (crate) mod picture_code;
+pub (crate) mod genre_code;
+pub (crate) mod rating_code;
+pub (crate) mod volume_code;
\ No newline at end of file
@@ -0,0 +1 @@
+pub (crate) mod rating_scale;
\ No newline at end of file
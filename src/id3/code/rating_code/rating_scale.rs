@@ -0,0 +1,58 @@
+/// Mapping between the 0-255 rating of the popularimeter and the 0-5 stars of the players
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RatingScale {
+    /// Windows Media Player : whole stars written as 1, 64, 128, 196 and 255
+    WindowsMediaPlayer,
+    /// MediaMonkey : half stars, the whole stars being written as Windows Media Player does
+    MediaMonkey,
+    /// foobar2000 : whole stars written as 1, 64, 128, 196 and 255, read with wider bounds
+    Foobar2000
+}
+
+/// Rating written for each half star, from 0.5 to 5 stars
+const MEDIA_MONKEY_RATINGS : [u8; 10] = [13, 1, 54, 64, 118, 128, 186, 196, 242, 255];
+/// Rating written for each whole star, from 1 to 5 stars
+const WHOLE_STAR_RATINGS : [u8; 5] = [1, 64, 128, 196, 255];
+/// Lowest rating read as each whole star, from 2 to 5 stars
+const WMP_LOWER_BOUNDS : [u8; 4] = [32, 96, 160, 224];
+const FOOBAR2000_LOWER_BOUNDS : [u8; 4] = [51, 114, 169, 219];
+
+impl RatingScale {
+    /// Returns the number of stars of `rating`, `None` if the rating is 0 (unrated)
+    pub fn stars(&self, rating : u8) -> Option<f32> {
+        if rating == 0 {
+            return None;
+        }
+        let whole_stars = |bounds : &[u8; 4]| 1 + bounds.iter().filter(|bound| rating >= **bound).count();
+        let stars = match self {
+            Self::WindowsMediaPlayer => whole_stars(&WMP_LOWER_BOUNDS) as f32,
+            Self::Foobar2000 => whole_stars(&FOOBAR2000_LOWER_BOUNDS) as f32,
+            Self::MediaMonkey => {
+                // Closest value written by MediaMonkey
+                let index = MEDIA_MONKEY_RATINGS.iter()
+                .enumerate()
+                .min_by_key(|(_, value)| (**value as i16 - rating as i16).abs())
+                .map(|(index, _)| index)?;
+                (index + 1) as f32 / 2.0
+            }
+        };
+        Some(stars)
+    }
+
+    /// Returns the rating written for `stars`, rounded to the precision of the player
+    ///
+    /// 0 stars gives the rating 0 (unrated)
+    pub fn rating(&self, stars : f32) -> u8 {
+        let stars = stars.clamp(0.0, 5.0);
+        match self {
+            Self::MediaMonkey => {
+                let half_stars = (stars * 2.0).round() as usize;
+                if half_stars == 0 { 0 } else { MEDIA_MONKEY_RATINGS[half_stars - 1] }
+            }
+            Self::WindowsMediaPlayer | Self::Foobar2000 => {
+                let whole_stars = stars.round() as usize;
+                if whole_stars == 0 { 0 } else { WHOLE_STAR_RATINGS[whole_stars - 1] }
+            }
+        }
+    }
+}
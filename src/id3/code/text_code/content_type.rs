@@ -1,8 +1,7 @@
-#[allow(dead_code)]
-
+/// Content type of the synchronised lyrics and text
 #[repr(u8)]
-#[derive(Clone, Copy)]
-pub(crate) enum TextContent {
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TextContent {
     Other = 0x00,
     Lyrics,
     TextTranscription,
@@ -13,7 +12,6 @@ pub(crate) enum TextContent {
 }
 
 impl TextContent {
-    #[allow(dead_code)]
     pub(crate) fn from_raw_value(value : u8) -> Option<Self> {
         match value {
             0 => Some(Self::Other),
@@ -26,4 +24,4 @@ impl TextContent {
             _ => None
         }
     }
-}
\ No newline at end of file
+}
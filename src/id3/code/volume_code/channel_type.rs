@@ -0,0 +1,31 @@
+/// Channel of a relative volume adjustment
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChannelType {
+    Other = 0x00,
+    MasterVolume,
+    FrontRight,
+    FrontLeft,
+    BackRight,
+    BackLeft,
+    FrontCentre,
+    BackCentre,
+    Subwoofer
+}
+
+impl ChannelType {
+    pub(crate) fn from_raw_value(value : u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Other),
+            1 => Some(Self::MasterVolume),
+            2 => Some(Self::FrontRight),
+            3 => Some(Self::FrontLeft),
+            4 => Some(Self::BackRight),
+            5 => Some(Self::BackLeft),
+            6 => Some(Self::FrontCentre),
+            7 => Some(Self::BackCentre),
+            8 => Some(Self::Subwoofer),
+            _ => None
+        }
+    }
+}
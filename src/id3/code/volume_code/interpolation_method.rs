@@ -0,0 +1,19 @@
+/// Interpolation between the adjustment points of an equalisation (EQU2)
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterpolationMethod {
+    /// No interpolation, the adjustment applies from its frequency to the next point
+    Band = 0x00,
+    /// Linear interpolation between the adjustment points
+    Linear = 0x01
+}
+
+impl InterpolationMethod {
+    pub(crate) fn from_raw_value(value : u8) -> Option<Self> {
+        match value {
+            0 => Some(Self::Band),
+            1 => Some(Self::Linear),
+            _ => None
+        }
+    }
+}
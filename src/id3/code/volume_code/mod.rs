@@ -0,0 +1,2 @@
+pub (crate) mod channel_type;
+pub (crate) mod interpolation_method;
\ No newline at end of file
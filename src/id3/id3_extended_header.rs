@@ -0,0 +1,268 @@
+use std::convert::TryInto;
+
+use crate::util::function::{synchsafe, unsynchsafe};
+
+/// CRC data present (ID3v2.3 flags, first byte)
+const V23_CRC_FLAG : u8 = 0b10_000_000;
+/// Tag is an update (ID3v2.4 flags)
+const V24_UPDATE_FLAG : u8 = 0b01_000_000;
+/// CRC data present (ID3v2.4 flags)
+const V24_CRC_FLAG : u8 = 0b00_100_000;
+/// Tag restrictions (ID3v2.4 flags)
+const V24_RESTRICTIONS_FLAG : u8 = 0b00_010_000;
+
+/// Maximum number of frames and tag size allowed by [TagRestrictions]
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum TagSizeRestriction {
+    /// No more than 128 frames and 1 MB total tag size
+    #[default]
+    Frames128Size1MB = 0b00,
+    /// No more than 64 frames and 128 KB total tag size
+    Frames64Size128KB = 0b01,
+    /// No more than 32 frames and 40 KB total tag size
+    Frames32Size40KB = 0b10,
+    /// No more than 32 frames and 4 KB total tag size
+    Frames32Size4KB = 0b11
+}
+
+/// Maximum length of the text fields allowed by [TagRestrictions]
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum TextSizeRestriction {
+    /// No restrictions
+    #[default]
+    NoRestriction = 0b00,
+    /// No string is longer than 1024 characters
+    Characters1024 = 0b01,
+    /// No string is longer than 128 characters
+    Characters128 = 0b10,
+    /// No string is longer than 30 characters
+    Characters30 = 0b11
+}
+
+/// Size of the images allowed by [TagRestrictions]
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ImageSizeRestriction {
+    /// No restrictions
+    #[default]
+    NoRestriction = 0b00,
+    /// All images are 256x256 pixels or smaller
+    Max256x256 = 0b01,
+    /// All images are 64x64 pixels or smaller
+    Max64x64 = 0b10,
+    /// All images are exactly 64x64 pixels, unless required otherwise
+    Exactly64x64 = 0b11
+}
+
+/// Restrictions of an ID3v2.4 tag, as declared in its extended header
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct TagRestrictions {
+    /// Maximum number of frames and tag size
+    pub tag_size : TagSizeRestriction,
+    /// Only ISO-8859-1 and UTF-8 are used
+    pub text_encoding : bool,
+    /// Maximum length of the text fields
+    pub text_size : TextSizeRestriction,
+    /// Images are only encoded in PNG or JPEG
+    pub image_encoding : bool,
+    /// Maximum size of the images
+    pub image_size : ImageSizeRestriction
+}
+
+impl TagRestrictions {
+    /// Decode the restrictions byte `%ppqrrstt`
+    pub(crate) fn from_byte(byte : u8) -> Self {
+        let tag_size = match byte >> 6 {
+            0b00 => TagSizeRestriction::Frames128Size1MB,
+            0b01 => TagSizeRestriction::Frames64Size128KB,
+            0b10 => TagSizeRestriction::Frames32Size40KB,
+            _ => TagSizeRestriction::Frames32Size4KB
+        };
+        let text_size = match (byte >> 3) & 0b11 {
+            0b00 => TextSizeRestriction::NoRestriction,
+            0b01 => TextSizeRestriction::Characters1024,
+            0b10 => TextSizeRestriction::Characters128,
+            _ => TextSizeRestriction::Characters30
+        };
+        let image_size = match byte & 0b11 {
+            0b00 => ImageSizeRestriction::NoRestriction,
+            0b01 => ImageSizeRestriction::Max256x256,
+            0b10 => ImageSizeRestriction::Max64x64,
+            _ => ImageSizeRestriction::Exactly64x64
+        };
+        Self {
+            tag_size,
+            text_encoding: byte & 0b0010_0000 != 0,
+            text_size,
+            image_encoding: byte & 0b0000_0100 != 0,
+            image_size
+        }
+    }
+
+    pub(crate) fn as_byte(&self) -> u8 {
+        ((self.tag_size as u8) << 6)
+        | ((self.text_encoding as u8) << 5)
+        | ((self.text_size as u8) << 3)
+        | ((self.image_encoding as u8) << 2)
+        | (self.image_size as u8)
+    }
+}
+
+/// Extended header of an ID3v2.3 or ID3v2.4 tag
+///
+/// The CRC-32 stored in the file can be read with [ID3ExtendedHeader::crc]. When the tag is written,
+/// a new CRC is computed over the frames if [ID3ExtendedHeader::has_crc] is set.
+///
+/// The update flag and the restrictions only exist in ID3v2.4 and are ignored when writing an ID3v2.3 tag
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ID3ExtendedHeader {
+    padding_size : u32,
+    has_crc : bool,
+    crc : Option<u32>,
+    is_update : bool,
+    restrictions : Option<TagRestrictions>
+}
+
+impl ID3ExtendedHeader {
+    /// Create an extended header without CRC, update flag nor restrictions
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse the extended header at the beginning of `buffer` and remove it from the buffer
+    pub(crate) fn from_buffer(buffer : &mut Vec<u8>, version : u8) -> Option<Self> {
+        if version == 4 {
+            Self::from_v24_buffer(buffer)
+        } else {
+            Self::from_v23_buffer(buffer)
+        }
+    }
+
+    fn from_v23_buffer(buffer : &mut Vec<u8>) -> Option<Self> {
+        let size = u32::from_be_bytes(buffer.get(0..4)?.try_into().ok()?) as usize;
+        if size < 6 || buffer.len() < size + 4 {
+            return None;
+        }
+        let bytes = buffer.drain(0..(size + 4)).collect::<Vec<u8>>();
+        let padding_size = u32::from_be_bytes(bytes[6..10].try_into().ok()?);
+        let has_crc = bytes[4] & V23_CRC_FLAG != 0;
+        let crc = if has_crc {
+            Some(u32::from_be_bytes(bytes.get(10..14)?.try_into().ok()?))
+        } else {
+            None
+        };
+        Some(Self { padding_size, has_crc, crc, is_update: false, restrictions: None })
+    }
+
+    fn from_v24_buffer(buffer : &mut Vec<u8>) -> Option<Self> {
+        let size = unsynchsafe(u32::from_be_bytes(buffer.get(0..4)?.try_into().ok()?)) as usize;
+        if size < 6 || buffer.len() < size {
+            return None;
+        }
+        let bytes = buffer.drain(0..size).collect::<Vec<u8>>();
+        let flag_bytes = bytes[4] as usize;
+        let flags = *bytes.get(5)?;
+        // Each flag is followed by its data, preceded by the length of the data
+        let mut data = bytes.get((5 + flag_bytes)..)?.iter();
+        let mut header = Self::new();
+        if flags & V24_UPDATE_FLAG != 0 {
+            header.is_update = true;
+            let _ = data.next()?;
+        }
+        if flags & V24_CRC_FLAG != 0 {
+            let _ = data.next()?;
+            let mut crc = 0u64;
+            for _ in 0..5 {
+                crc = (crc << 7) | (*data.next()? & 0x7F) as u64;
+            }
+            header.has_crc = true;
+            header.crc = Some(crc as u32);
+        }
+        if flags & V24_RESTRICTIONS_FLAG != 0 {
+            let _ = data.next()?;
+            header.restrictions = Some(TagRestrictions::from_byte(*data.next()?));
+        }
+        Some(header)
+    }
+
+    /// Returns the raw bytes of the extended header for the tag `version`
+    pub(crate) fn as_bytes(&self, version : u8, padding_size : u32, crc : u32) -> Vec<u8> {
+        let mut bytes = vec![];
+        if version == 4 {
+            let mut flags = 0;
+            let mut data = vec![];
+            if self.is_update {
+                flags |= V24_UPDATE_FLAG;
+                data.push(0);
+            }
+            if self.has_crc {
+                flags |= V24_CRC_FLAG;
+                data.push(5);
+                // 35 bits synchsafe integer
+                data.extend((0..5).rev().map(|i| ((crc as u64 >> (7 * i)) & 0x7F) as u8));
+            }
+            if let Some(restrictions) = self.restrictions {
+                flags |= V24_RESTRICTIONS_FLAG;
+                data.push(1);
+                data.push(restrictions.as_byte());
+            }
+            let size = 6 + data.len() as u32;
+            bytes.extend_from_slice(&synchsafe(size).to_be_bytes());
+            bytes.push(1);
+            bytes.push(flags);
+            bytes.append(&mut data);
+        } else {
+            let size : u32 = if self.has_crc { 10 } else { 6 };
+            bytes.extend_from_slice(&size.to_be_bytes());
+            bytes.push(if self.has_crc { V23_CRC_FLAG } else { 0 });
+            bytes.push(0);
+            bytes.extend_from_slice(&padding_size.to_be_bytes());
+            if self.has_crc {
+                bytes.extend_from_slice(&crc.to_be_bytes());
+            }
+        }
+        bytes
+    }
+
+    /// Size in bytes of the extended header for the tag `version`
+    pub(crate) fn size(&self, version : u8) -> u32 {
+        self.as_bytes(version, 0, 0).len() as u32
+    }
+}
+
+impl ID3ExtendedHeader {
+    /// Returns the padding size declared in an ID3v2.3 extended header
+    pub fn padding_size(&self) -> u32 {
+        self.padding_size
+    }
+    /// Whether a CRC-32 of the frames is stored in the extended header
+    pub fn has_crc(&self) -> bool {
+        self.has_crc
+    }
+    /// Store a CRC-32 of the frames when the tag is written
+    pub fn set_crc(&mut self, crc : bool) {
+        self.has_crc = crc
+    }
+    /// Returns the CRC-32 read from the file
+    pub fn crc(&self) -> Option<u32> {
+        self.crc
+    }
+    /// Whether the tag is an update of a tag found earlier in the file (ID3v2.4)
+    pub fn is_update(&self) -> bool {
+        self.is_update
+    }
+    /// Set the update flag (ID3v2.4)
+    pub fn set_update(&mut self, update : bool) {
+        self.is_update = update
+    }
+    /// Returns the tag restrictions (ID3v2.4)
+    pub fn restrictions(&self) -> Option<TagRestrictions> {
+        self.restrictions
+    }
+    /// Set the tag restrictions (ID3v2.4)
+    pub fn set_restrictions(&mut self, restrictions : Option<TagRestrictions>) {
+        self.restrictions = restrictions
+    }
+}
@@ -1,14 +1,19 @@
-use crate::id3::id3_frame_value::FrameValue::NoValue;
 use std::convert::TryInto;
-use std::str::FromStr;
 
 use crate::id3::id3_frameid::ID3FRAMEID;
-use crate::id3::id3_header_flag::{ID3FRAMEHEADERFLAGSB1, ID3FRAMEHEADERFLAGSB2};
-use crate::id3::id3_header_flag::ID3FRAMEHEADERFLAGSB1::*;
+use crate::id3::id3_header_flag::{FrameStatusFlags, ID3FRAMEHEADERFLAGSB1, ID3FRAMEHEADERFLAGSB2};
 use crate::id3::id3_header_flag::ID3FRAMEHEADERFLAGSB2::*;
+use crate::util::file_format::PictureFormat;
+use crate::util::function::{self, deunsynchronise, synchsafe, unsynchsafe};
+use crate::util::number::u24;
+use crate::util::reading_mode::NULL_TERMINATE;
 use crate::util::traits::{FrameSize, RawSize};
+use crate::util::zlib::{deflate, inflate};
 
-use super::id3_frame_value::{AttachedPictureFrame, CommentFrame, FrameValue, TextFrame, UnsyncLyricsFrame};
+use super::id3_frame_value::{AttachedPictureFrame, CommentFrame, Equalisation2Frame, EqualiisationFrame, EventTimingFrame, FrameValue, GeneralEncapsulatedObjectFrame, InvolvedPeopleFrame, LinkedInfoFrame, MusicCdIdframe, PositionSyncFrame, PrivateFrame, LocationLookupTableFrame, PlayCounterFrame, PopularimeterFrame, RelativeVolumeAdjustementFrame, ReverbFrame, SyncLyricsFrame, SyncTempoCodeFrame, TextFrame, UniqueFileIdentifierFrame, UnsyncLyricsFrame, UrlFrame, UserInfoFrame, UserUrlFrame};
+
+/// Largest decompressed size of a frame without data length, the maximum size of an ID3v2 tag (256 MB)
+const MAX_DECOMPRESSED_SIZE : usize = 1 << 28;
 
 pub(crate) struct ID3FRAME {
     frame_id : ID3FRAMEID,
@@ -16,6 +21,15 @@ pub(crate) struct ID3FRAME {
     size : u32,
     _flag_byte_1 : Vec<ID3FRAMEHEADERFLAGSB1>,
     _flag_byte_2 : Vec<ID3FRAMEHEADERFLAGSB2>,
+    /// Group symbol if [GroupingIdentity] is set
+    group_id : Option<u8>,
+    /// Method symbol if [Encryption] is set
+    encryption_method : Option<u8>,
+    /// Decompressed size (ID3v2.3) or data length indicator (ID3v2.4)
+    /// of a frame which couldn't be decoded. Recomputed on write otherwise
+    data_length : Option<u32>,
+    /// Whether the value is kept as read, still compressed or encrypted
+    encoded : bool,
     value : FrameValue
 }
 
@@ -29,6 +43,10 @@ impl From<(ID3FRAMEID, FrameValue)> for ID3FRAME {
             size,
             _flag_byte_1,
             _flag_byte_2,
+            group_id: None,
+            encryption_method: None,
+            data_length: None,
+            encoded: false,
             value
         }
     }
@@ -36,72 +54,226 @@ impl From<(ID3FRAMEID, FrameValue)> for ID3FRAME {
 
 impl ID3FRAME {
 
-    
-    pub(crate) fn new(buffer: &mut Vec<u8>) -> Option<Self>{
-        //println!("buffer lenght : {}", buffer.len());
-        if buffer.len() <= 10 {
+    /// Parse a frame of an ID3v2.`version` tag
+    ///
+    /// `unsynchronised` : whether the tag header has the unsynchronisation flag
+    ///
+    /// Returns `None` if the buffer is exhausted or reaches the padding
+    pub(crate) fn new(buffer: &mut Vec<u8>, version : u8, unsynchronised : bool) -> Option<Self>{
+        if version == 2 {
+            return Self::new_v22(buffer);
+        }
+        if buffer.len() < 10 || buffer[0] == 0 {
             return None;
         }
-        let s = String::from_utf8(buffer.drain(0..4).collect()).unwrap();
-        let frame_id = ID3FRAMEID::from_str(s.as_str()).ok()?;
-        let size = u32::from_be_bytes(buffer.drain(0..4).collect::<Vec<u8>>().try_into().unwrap());
-        if (size + 2) as usize >= buffer.len() { return None; }
-        let mut _flag_byte_1 = vec![];
-        let mut _flag_byte_2 = vec![];
+        let frame_id = ID3FRAMEID::from_raw_id(&buffer[0..4]).ok()?;
+        let raw_size = u32::from_be_bytes(buffer[4..8].try_into().unwrap());
+        let size = if version == 4 { unsynchsafe(raw_size) } else { raw_size };
+        if size as usize + 10 > buffer.len() { return None; }
+        buffer.drain(0..8);
         let flag1 = buffer.remove(0);
         let flag2 = buffer.remove(0);
-        let frame_size = size + 10;
-        // println!("{} ->  size : {} ", frame_id, frame_size);
-        if (flag1 & (FileAlterPreservation as u8) ) == (FileAlterPreservation as u8) {
-            _flag_byte_1.push(FileAlterPreservation)
-        }
-        
-        if (flag1 & (TagAlterPreservation as u8)) == (TagAlterPreservation as u8) {
-            _flag_byte_1.push(TagAlterPreservation)
-        }
-        
-        if (flag1 & (ReadOnly as u8)) == (ReadOnly as u8) {
-            _flag_byte_1.push(ReadOnly)
+        let _flag_byte_1 = ID3FRAMEHEADERFLAGSB1::from_flag_byte(flag1, version);
+        let mut _flag_byte_2 = ID3FRAMEHEADERFLAGSB2::from_flag_byte(flag2, version);
+        let mut body = buffer.drain(0..(size as usize)).collect::<Vec<u8>>();
+        if version == 4 && (unsynchronised || _flag_byte_2.contains(&Unsynchronisation)) {
+            body = deunsynchronise(&body);
+            if !_flag_byte_2.contains(&Unsynchronisation) {
+                _flag_byte_2.push(Unsynchronisation)
+            }
         }
-        
-        if (flag2 & (Compression as u8) ) == Compression as u8  {
-            _flag_byte_2.push(Compression)
-        }
-        if (flag2 & Encryption as u8)  == Encryption as u8  {
-            _flag_byte_2.push(Encryption);
-        }
-        
-        if (flag2 & (GroupingIdentity) as u8) == (GroupingIdentity as u8) {
-            _flag_byte_2.push(GroupingIdentity);
+
+        let mut group_id = None;
+        let mut encryption_method = None;
+        let mut data_length = None;
+        if version == 4 {
+            if _flag_byte_2.contains(&GroupingIdentity) && !body.is_empty() {
+                group_id = Some(body.remove(0))
+            }
+            if _flag_byte_2.contains(&Encryption) && !body.is_empty() {
+                encryption_method = Some(body.remove(0))
+            }
+            if _flag_byte_2.contains(&DataLengthIndicator) && body.len() >= 4 {
+                let length = u32::from_be_bytes(body.drain(0..4).collect::<Vec<u8>>().try_into().unwrap());
+                data_length = Some(unsynchsafe(length))
+            }
+        } else {
+            if _flag_byte_2.contains(&Compression) && body.len() >= 4 {
+                data_length = Some(u32::from_be_bytes(body.drain(0..4).collect::<Vec<u8>>().try_into().unwrap()))
+            }
+            if _flag_byte_2.contains(&Encryption) && !body.is_empty() {
+                encryption_method = Some(body.remove(0))
+            }
+            if _flag_byte_2.contains(&GroupingIdentity) && !body.is_empty() {
+                group_id = Some(body.remove(0))
+            }
         }
-        let value = match FrameValue::new(buffer, frame_id, size){
-            Some(f) => f,
-            None => NoValue,
+
+        // Encrypted frames, unknown compressed frames and frames which can't be decompressed are kept as they are
+        let compressed = _flag_byte_2.contains(&Compression);
+        let decoded = if _flag_byte_2.contains(&Encryption) || (compressed && frame_id.is_unknown()) {
+            Err(body)
+        } else if compressed {
+            inflate(&body, data_length.map_or(MAX_DECOMPRESSED_SIZE, |length| length as usize)).ok_or(body)
+        } else {
+            Ok(body)
         };
-        Some(Self {
+        let encoded = decoded.is_err();
+        let value = match decoded {
+            Ok(mut body) => {
+                // The data length is recomputed from the value when writing
+                data_length = None;
+                _flag_byte_2.retain(|flag| *flag != DataLengthIndicator);
+                let body_size = body.len() as u32;
+                // A frame which can't be parsed is kept as it is
+                let raw = body.clone();
+                match FrameValue::new(&mut body, frame_id, body_size){
+                    Some(f) => f,
+                    None => FrameValue::Undefined(raw),
+                }
+            }
+            Err(body) => FrameValue::Undefined(body)
+        };
+        let mut frame = Self {
             frame_id,
-            size: frame_size,
+            size: 0,
             _flag_byte_1,
             _flag_byte_2,
+            group_id,
+            encryption_method,
+            data_length,
+            encoded,
             value
-        })
+        };
+        frame.recalcule_size();
+        Some(frame)
+    }
+
+    /// Parse a frame of an ID3v2.2 tag and map it to its ID3v2.3 counterpart
+    ///
+    /// The unknown frames, which have no ID3v2.3 identifier to be written back with, are skipped
+    fn new_v22(buffer: &mut Vec<u8>) -> Option<Self> {
+        loop {
+            if buffer.len() < 6 || buffer[0] == 0 {
+                return None;
+            }
+            let s = String::from_utf8(buffer[0..3].to_vec()).ok()?;
+            let size = u24::from_be_bytes(buffer[3..6].try_into().unwrap()).value();
+            if size as usize + 6 > buffer.len() { return None; }
+            let frame_id = match ID3FRAMEID::from_v22_str(s.as_str()) {
+                Ok(frame_id) => frame_id,
+                Err(_) if s.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()) => {
+                    buffer.drain(0..(size as usize + 6));
+                    continue;
+                }
+                Err(_) => return None
+            };
+            buffer.drain(0..6);
+            let mut body = buffer.drain(0..(size as usize)).collect::<Vec<u8>>();
+            if frame_id == ID3FRAMEID::APIC && body.len() >= 4 {
+                // PIC : the 3 characters image format is replaced by a mime type
+                let image_format = String::from_utf8_lossy(&body[1..4]).to_string();
+                let mut mime_type = if image_format == "-->" {
+                    image_format
+                } else {
+                    PictureFormat::from_image_format(&image_format).to_mime_string()
+                }.into_bytes();
+                mime_type.push(NULL_TERMINATE);
+                body.splice(1..4, mime_type);
+            }
+            if frame_id == ID3FRAMEID::LINK && body.len() >= 3 {
+                // LNK : the identifier of the linked frame has 3 characters
+                let linked_id = String::from_utf8_lossy(&body[0..3]).to_string();
+                match ID3FRAMEID::from_v22_str(&linked_id) {
+                    Ok(linked_id) => { body.splice(0..3, linked_id.to_string().into_bytes()); }
+                    Err(_) => continue
+                }
+            }
+            let body_size = body.len() as u32;
+            // A frame which can't be parsed is kept as it is
+            let raw = body.clone();
+            let value = match FrameValue::new(&mut body, frame_id, body_size) {
+                Some(f) => f,
+                None => FrameValue::Undefined(raw),
+            };
+            let mut frame : Self = (frame_id, value).into();
+            frame.recalcule_size();
+            return Some(frame);
+        }
+    }
+
+    /// Bytes appended to the frame header according to the flags
+    fn header_extension(&self, version : u8, data_length : Option<u32>, compressed : bool) -> Vec<u8> {
+        let mut bytes = vec![];
+        if version == 4 {
+            if let Some(group_id) = self.group_id {
+                bytes.push(group_id)
+            }
+            if let Some(method) = self.encryption_method {
+                bytes.push(method)
+            }
+            if let Some(length) = data_length {
+                bytes.append(&mut synchsafe(length).to_be_bytes().to_vec())
+            }
+        } else {
+            if let (Some(length), true) = (data_length, compressed) {
+                bytes.append(&mut length.to_be_bytes().to_vec())
+            }
+            if let Some(method) = self.encryption_method {
+                bytes.push(method)
+            }
+            if let Some(group_id) = self.group_id {
+                bytes.push(group_id)
+            }
+        }
+        bytes
     }
 
-    pub (crate) fn as_bytes(&self) -> Vec<u8> {
+    /// Returns the frame raw bytes for an ID3v2.`version` tag
+    ///
+    /// `unsynchronise` : apply the unsynchronisation scheme to the frame (ID3v2.4 only),
+    /// even if the frame doesn't have the flag
+    ///
+    /// `compress` : compress the frame with zlib, even if the frame doesn't have the flag.
+    /// A frame kept compressed or encrypted is written as it was read
+    pub (crate) fn as_bytes(&self, version : u8, unsynchronise : bool, compress : bool) -> Vec<u8> {
         let mut bytes = vec![];
+        let mut value = self.value.raw_bytes();
+        let mut data_length = self.data_length;
+        let compressed = self._flag_byte_2.contains(&Compression) || (compress && !self.encoded);
+        if compressed && !self.encoded {
+            data_length = Some(value.len() as u32);
+            value = deflate(&value);
+        }
+        let mut body = self.header_extension(version, data_length, compressed);
+        body.append(&mut value);
+        let unsynchronise = version == 4 && (unsynchronise || self._flag_byte_2.contains(&Unsynchronisation));
+        if unsynchronise {
+            body = function::unsynchronise(&body);
+        }
+        let size = if version == 4 { synchsafe(body.len() as u32) } else { body.len() as u32 };
         bytes.append(&mut self.frame_id.to_string().into_bytes());
-        bytes.append(&mut (self.size - 10).to_be_bytes().to_vec());
+        bytes.append(&mut size.to_be_bytes().to_vec());
         let mut flag1 = 0;
         let mut flag2 = 0;
         for flag in self._flag_byte_1.iter(){
-            flag1 |= *flag as u8
+            flag1 |= flag.raw_value(version)
         }
         for flag in self._flag_byte_2.iter(){
-            flag2 |= *flag as u8
-        } 
+            flag2 |= flag.raw_value(version)
+        }
+        if compressed {
+            flag2 |= Compression.raw_value(version)
+        }
+        if version == 4 && data_length.is_some() {
+            flag2 |= DataLengthIndicator.raw_value(version)
+        }
+        if unsynchronise {
+            flag2 |= Unsynchronisation.raw_value(version)
+        }
         bytes.push(flag1);
         bytes.push(flag2);
-        bytes.append(&mut self.value.raw_bytes());
+        bytes.append(&mut body);
         bytes
     }
 }
@@ -115,7 +287,51 @@ impl FrameSize for ID3FRAME {
 impl ID3FRAME {
 
     pub(crate) fn recalcule_size(&mut self) {
-        self.size = self.value.raw_size() as u32 + 10
+        let extension_size = self.group_id.map_or(0, |_| 1)
+            + self.encryption_method.map_or(0, |_| 1)
+            + self.data_length.map_or(0, |_| 4);
+        self.size = self.value.raw_size() as u32 + extension_size + 10
+    }
+
+    /// Drop what can't be represented in an ID3v2.`version` tag
+    pub(crate) fn adapt_to_version(&mut self, version : u8) {
+        if version == 3 {
+            self._flag_byte_2.retain(|flag| flag.raw_value(version) != 0);
+            if !self._flag_byte_2.contains(&Compression) {
+                self.data_length = None
+            }
+        }
+        self.value.adapt_text_encoding(version);
+        self.recalcule_size()
+    }
+
+    pub(crate) fn status_flags(&self) -> FrameStatusFlags {
+        FrameStatusFlags::from_flags(&self._flag_byte_1)
+    }
+
+    pub(crate) fn set_status_flags(&mut self, flags : FrameStatusFlags) {
+        self._flag_byte_1 = flags.to_flags()
+    }
+
+    /// Clear the discard on tag alteration flag of a frame whose content has been rewritten
+    /// and update its size
+    pub(crate) fn mark_rewritten(&mut self) {
+        let mut flags = self.status_flags();
+        flags.discard_on_tag_alteration = false;
+        self.set_status_flags(flags);
+        self.recalcule_size()
+    }
+
+    /// Whether the frame has the read only flag
+    pub(crate) fn is_read_only(&self) -> bool {
+        self._flag_byte_1.contains(&ID3FRAMEHEADERFLAGSB1::ReadOnly)
+    }
+
+    /// Whether the frame is worth compressing when the tag is written with
+    /// a compression threshold of `threshold` bytes
+    pub(crate) fn should_compress(&self, threshold : usize) -> bool {
+        matches!(self.frame_id, ID3FRAMEID::APIC | ID3FRAMEID::GEOB | ID3FRAMEID::PRIV)
+        && self.value.raw_size() >= threshold
     }
 
     pub (crate) fn get_frame_id(&self) -> &ID3FRAMEID {
@@ -145,6 +361,78 @@ impl ID3FRAME {
         self.value.as_unsynchroned_lyrics_frame_mut()
     }
 
+    pub (crate) fn as_sync_lyrics_frame(&self) -> Option<&SyncLyricsFrame> {
+        self.value.as_sync_lyrics_frame()
+    }
+    #[allow(dead_code)]
+    pub (crate) fn as_sync_lyrics_frame_mut(&mut self) -> Option<&mut SyncLyricsFrame> {
+        self.value.as_sync_lyrics_frame_mut()
+    }
+
+    pub(crate) fn as_event_timing_frame(&self) -> Option<&EventTimingFrame> {
+        self.value.as_event_timing_frame()
+    }
+    pub(crate) fn as_sync_tempo_code_frame(&self) -> Option<&SyncTempoCodeFrame> {
+        self.value.as_sync_tempo_code_frame()
+    }
+    pub(crate) fn as_location_lookup_table_frame(&self) -> Option<&LocationLookupTableFrame> {
+        self.value.as_location_lookup_table_frame()
+    }
+    pub(crate) fn as_relative_volume_frame(&self) -> Option<&RelativeVolumeAdjustementFrame> {
+        self.value.as_relative_volume_frame()
+    }
+    pub(crate) fn as_user_info_frame(&self) -> Option<&UserInfoFrame> {
+        self.value.as_user_info_frame()
+    }
+    pub(crate) fn as_linked_info_frame(&self) -> Option<&LinkedInfoFrame> {
+        self.value.as_linked_info_frame()
+    }
+    pub(crate) fn as_position_sync_frame(&self) -> Option<&PositionSyncFrame> {
+        self.value.as_position_sync_frame()
+    }
+    pub(crate) fn as_music_cd_id_frame(&self) -> Option<&MusicCdIdframe> {
+        self.value.as_music_cd_id_frame()
+    }
+    pub(crate) fn as_involved_people_frame(&self) -> Option<&InvolvedPeopleFrame> {
+        self.value.as_involved_people_frame()
+    }
+    pub(crate) fn as_url_frame(&self) -> Option<&UrlFrame> {
+        self.value.as_url_frame()
+    }
+    pub(crate) fn as_user_url_frame(&self) -> Option<&UserUrlFrame> {
+        self.value.as_user_url_frame()
+    }
+    pub(crate) fn as_unique_file_identifier_frame(&self) -> Option<&UniqueFileIdentifierFrame> {
+        self.value.as_unique_file_identifier_frame()
+    }
+    pub(crate) fn as_private_frame(&self) -> Option<&PrivateFrame> {
+        self.value.as_private_frame()
+    }
+    pub(crate) fn as_encapsulated_object_frame(&self) -> Option<&GeneralEncapsulatedObjectFrame> {
+        self.value.as_encapsulated_object_frame()
+    }
+    pub(crate) fn as_equalisation_frame(&self) -> Option<&EqualiisationFrame> {
+        self.value.as_equalisation_frame()
+    }
+    pub(crate) fn as_equalisation2_frame(&self) -> Option<&Equalisation2Frame> {
+        self.value.as_equalisation2_frame()
+    }
+    pub(crate) fn as_reverb_frame(&self) -> Option<&ReverbFrame> {
+        self.value.as_reverb_frame()
+    }
+    pub(crate) fn as_play_counter_frame(&self) -> Option<&PlayCounterFrame> {
+        self.value.as_play_counter_frame()
+    }
+    pub(crate) fn as_play_counter_frame_mut(&mut self) -> Option<&mut PlayCounterFrame> {
+        self.value.as_play_counter_frame_mut()
+    }
+    pub(crate) fn as_popularimeter_frame(&self) -> Option<&PopularimeterFrame> {
+        self.value.as_popularimeter_frame()
+    }
+    pub(crate) fn as_popularimeter_frame_mut(&mut self) -> Option<&mut PopularimeterFrame> {
+        self.value.as_popularimeter_frame_mut()
+    }
+
     pub (crate) fn as_comment_frame(&self) -> Option<&CommentFrame>{
         self.value.as_comment_frame()
     }
@@ -1,11 +1,26 @@
-use crate::util::{traits::{RawSize, ToBytes, StringConvert, SplitString, ToU32, ToU16}, reading_mode::{TextEncoding, NULL_TERMINATE}, file_format::PictureFormat};
+use std::convert::TryInto;
 
-use super::{code::{event_timing_code::time_stamp_format::TimeStampFormat, picture_code::picture_type:: PictureType}, id3_frameid::ID3FRAMEID};
+use crate::util::{cd_toc::CdToc, traits::{RawSize, ToBytes, StringConvert, SplitString, ToU32, ToU16}, reading_mode::TextEncoding, file_format::PictureFormat};
+
+use super::{code::{event_timing_code::{event_type::EventType, time_stamp_format::TimeStampFormat}, picture_code::picture_type:: PictureType, text_code::content_type::TextContent, volume_code::{channel_type::ChannelType, interpolation_method::InterpolationMethod}}, id3_frameid::ID3FRAMEID};
 use super::id3_frameid::ID3FRAMEID::*;
+use super::id3_lrc::{format_lrc, parse_lrc};
+use super::id3_private::PrivateContent;
 
+/// Encode `text`, preceded by a byte order mark in UTF-16
+fn encode_string(text : &str, encoding : &TextEncoding, null_terminated : bool) -> Vec<u8> {
+    match encoding {
+        TextEncoding::UnicodeUtf16 if !text.starts_with('\u{feff}') => format!("\u{feff}{}", text).to_bytes(encoding, null_terminated),
+        _ => text.to_string().to_bytes(encoding, null_terminated)
+    }
+}
 
 
-pub(crate) struct UniqueFileIdentifierFrame {
+/// Unique file identifier (UFID)
+///
+/// An identifier of the file in the database of its owner, at most one frame per owner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UniqueFileIdentifierFrame {
     owner_id : String,
     id : Vec<u8>
 }
@@ -15,12 +30,33 @@ impl RawSize for UniqueFileIdentifierFrame {
     }
     fn raw_bytes(&self) -> Vec<u8> {
         let mut bytes = vec![];
-        bytes.append(&mut self.owner_id.clone().into_bytes());
-        bytes.push(NULL_TERMINATE);
+        bytes.append(&mut self.owner_id.to_bytes(&TextEncoding::Iso8859_1, true));
         bytes.append(&mut self.id.clone());
         bytes
     }
 }
+impl UniqueFileIdentifierFrame {
+    /// Maximum size of an identifier
+    pub const MAX_ID_SIZE : usize = 64;
+
+    /// Create a unique file identifier
+    ///
+    /// `None` if `owner_id` is empty or `id` is longer than [UniqueFileIdentifierFrame::MAX_ID_SIZE] bytes
+    pub fn new(owner_id : &str, id : &[u8]) -> Option<Self> {
+        if owner_id.is_empty() || id.len() > Self::MAX_ID_SIZE {
+            return None;
+        }
+        Some(Self { owner_id: owner_id.into(), id: id.to_vec() })
+    }
+    /// Returns the owner identifier, usually an URL
+    pub fn owner_id(&self) -> &str {
+        &self.owner_id
+    }
+    /// Returns the identifier
+    pub fn id(&self) -> &Vec<u8> {
+        &self.id
+    }
+}
 
 pub(crate) struct TextFrame {
     text_encoding : TextEncoding,
@@ -34,7 +70,7 @@ impl RawSize for TextFrame {
     fn raw_bytes(&self) -> Vec<u8> {
         let mut bytes = vec![];
         bytes.push(self.text_encoding as u8);
-        bytes.append(&mut self.text.to_bytes(&self.text_encoding, false));
+        bytes.append(&mut encode_string(&self.text, &self.text_encoding, false));
         bytes
     }
 }
@@ -81,31 +117,102 @@ impl RawSize for UserInfoFrame {
     fn raw_bytes(&self) -> Vec<u8> {
         let mut bytes = vec![];
         bytes.push(self.text_encoding as u8);
-        bytes.append(&mut self.description.to_bytes(&self.text_encoding, true));
-        bytes.append(&mut self.text.to_bytes(&self.text_encoding, false));
+        bytes.append(&mut encode_string(&self.description, &self.text_encoding, true));
+        bytes.append(&mut encode_string(&self.text, &self.text_encoding, false));
         // bytes.push(NULL_TERMINATE);
         // bytes.push(NULL_TERMINATE);
         bytes
     }
     
 }
+impl UserInfoFrame {
+    pub (crate) fn new(tag_version: u8, description: String, text: String) -> Self {
+        let text_frame = TextFrame::new(tag_version, format!("{}{}", description, text));
+        Self {
+            text_encoding: text_frame.text_encoding,
+            description,
+            text
+        }
+    }
+    /// Returns the description without its terminator and byte order mark
+    pub (crate) fn get_description(&self) -> &str {
+        self.description.trim_matches(|c| c == '\u{0}' || c == '\u{feff}')
+    }
+    /// Returns the text without its terminator and byte order mark
+    pub (crate) fn get_text(&self) -> &str {
+        self.text.trim_matches(|c| c == '\u{0}' || c == '\u{feff}')
+    }
+}
 
 pub(crate) struct UrlFrame {
     url : String
 }
 impl RawSize for UrlFrame {
     fn raw_size(&self) -> usize {
-        self.url.len()
+        self.raw_bytes().len()
+    }
+
+    fn raw_bytes(&self) -> Vec<u8> {
+        self.url.to_bytes(&TextEncoding::Iso8859_1, false)
+    }
+}
+impl UrlFrame {
+    pub(crate) fn new(url : &str) -> Self {
+        Self { url: url.into() }
+    }
+    pub(crate) fn url(&self) -> &str {
+        self.url.trim_end_matches('\u{0}')
+    }
+}
+
+/// User defined URL link (WXXX)
+///
+/// A URL identified by its description, which is unique in the tag
+#[derive(Debug, Clone)]
+pub struct UserUrlFrame {
+    text_encoding : TextEncoding,
+    description : String,
+    url : String
+}
+impl RawSize for UserUrlFrame {
+    fn raw_size(&self) -> usize {
+        self.raw_bytes().len()
     }
 
     fn raw_bytes(&self) -> Vec<u8> {
-        self.url.clone().into_bytes()
+        let mut bytes = vec![self.text_encoding as u8];
+        bytes.append(&mut encode_string(&self.description, &self.text_encoding, true));
+        bytes.append(&mut self.url.to_bytes(&TextEncoding::Iso8859_1, false));
+        bytes
+    }
+}
+impl UserUrlFrame {
+    /// Create a user defined URL link
+    pub fn new(description : &str, url : &str) -> Self {
+        let text_encoding = if description.is_ascii() { TextEncoding::Iso8859_1 } else { TextEncoding::UnicodeUtf16 };
+        Self { text_encoding, description: description.into(), url: url.into() }
+    }
+    fn from_bytes(mut data : Vec<u8>) -> Option<Self> {
+        let text_encoding = TextEncoding::from_raw_value(*data.first()?).unwrap_or(TextEncoding::Iso8859_1);
+        data.remove(0);
+        let description = data.take_string(&text_encoding)?;
+        let url = data.into_string(&TextEncoding::Iso8859_1)?.trim_end_matches('\u{0}').to_string();
+        Some(Self { text_encoding, description, url })
+    }
+    /// Returns the description of the link
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+    /// Returns the URL
+    pub fn url(&self) -> &str {
+        &self.url
     }
 }
 
+/// Involved people list (IPLS, ID3v2.3) as (role, person) pairs
 pub(crate) struct InvolvedPeopleFrame {
     text_encoding : TextEncoding,
-    people_list : String
+    people : Vec<(String, String)>
 }
 impl RawSize for InvolvedPeopleFrame {
     fn raw_size(&self) -> usize {
@@ -114,15 +221,56 @@ impl RawSize for InvolvedPeopleFrame {
     fn raw_bytes(&self) -> Vec<u8> {
         let mut bytes = vec![];
         bytes.push(self.text_encoding as u8);
-        bytes.append(&mut self.people_list.to_bytes(&self.text_encoding, false));
+        for (role, person) in self.people.iter() {
+            bytes.append(&mut encode_string(role, &self.text_encoding, true));
+            bytes.append(&mut encode_string(person, &self.text_encoding, true));
+        }
         bytes
     }
+}
+impl InvolvedPeopleFrame {
+    pub (crate) fn new(tag_version: u8, people: Vec<(String, String)>) -> Self {
+        let text_encoding = if people.iter().all(|(role, person)| role.is_ascii() && person.is_ascii()) {
+            TextEncoding::Iso8859_1
+        } else if tag_version == 3 {
+            TextEncoding::UnicodeUtf16
+        } else {
+            TextEncoding::UnicodeUtf8
+        };
+        Self { text_encoding, people }
+    }
+    fn from_bytes(mut data : Vec<u8>) -> Option<Self> {
+        let text_encoding = TextEncoding::from_raw_value(*data.first()?).unwrap_or(TextEncoding::Iso8859_1);
+        data.remove(0);
+        let mut strings = vec![];
+        while !data.is_empty() {
+            strings.push(data.take_string(&text_encoding)?)
+        }
+        Some(Self { text_encoding, people: people_from_strings(strings) })
+    }
+    pub (crate) fn people(&self) -> &Vec<(String, String)> {
+        &self.people
+    }
+}
 
-
+/// Group a list of strings into (role, person) pairs, a person missing at the end being empty
+pub(crate) fn people_from_strings(strings : Vec<String>) -> Vec<(String, String)> {
+    strings.chunks(2)
+    .map(|pair| (pair[0].clone(), pair.get(1).cloned().unwrap_or_default()))
+    .collect()
 }
+
 pub (crate) struct MusicCdIdframe {
     cd_toc : Vec<u8>
 }
+impl MusicCdIdframe {
+    pub (crate) fn new(cd_toc : &CdToc) -> Self {
+        Self { cd_toc: cd_toc.to_mcdi() }
+    }
+    pub (crate) fn cd_toc(&self) -> Option<CdToc> {
+        CdToc::from_mcdi(&self.cd_toc)
+    }
+}
 impl RawSize for MusicCdIdframe {
     fn raw_size(&self) -> usize {
         self.cd_toc.len()
@@ -132,51 +280,339 @@ impl RawSize for MusicCdIdframe {
     }
 }
 
-pub(crate) struct EventTimingFrame {
+/// Event timing codes (ETCO)
+///
+/// The events are a list of (event, timestamp) sorted by time, the timestamps being expressed in
+/// the unit given by [EventTimingFrame::time_stamp_format]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EventTimingFrame {
     time_stamp_format : TimeStampFormat,
-    raw : Vec<u8>
+    events : Vec<(EventType, u32)>
 }
 impl RawSize for EventTimingFrame {
     fn raw_size(&self) -> usize {
-        1 + self.raw.len()
+        self.raw_bytes().len()
     }
 
     fn raw_bytes(&self) -> Vec<u8> {
         let mut bytes = vec![];
         bytes.push(self.time_stamp_format as u8);
-        bytes.append(&mut self.raw.clone());
+        for (event, timestamp) in self.events.iter() {
+            bytes.append(&mut event.as_bytes());
+            bytes.extend_from_slice(&timestamp.to_be_bytes());
+        }
         bytes
     }
 }
+impl EventTimingFrame {
+    /// Create event timing codes, the events are sorted by timestamp
+    pub fn new(time_stamp_format : TimeStampFormat, events : Vec<(EventType, u32)>) -> Self {
+        let mut frame = Self { time_stamp_format, events: vec![] };
+        frame.set_events(events);
+        frame
+    }
+    /// Returns the unit of the timestamps
+    pub fn time_stamp_format(&self) -> TimeStampFormat {
+        self.time_stamp_format
+    }
+    /// Returns the events as a list of (event, timestamp)
+    pub fn events(&self) -> &Vec<(EventType, u32)> {
+        &self.events
+    }
+    /// Replace the events, which are sorted by timestamp
+    pub fn set_events(&mut self, mut events : Vec<(EventType, u32)>) {
+        events.sort_by_key(|(_, timestamp)| *timestamp);
+        self.events = events
+    }
+    /// Returns the timestamp of the first occurrence of `event`
+    pub fn timestamp(&self, event : &EventType) -> Option<u32> {
+        self.events.iter().find(|(e, _)| e == event).map(|(_, timestamp)| *timestamp)
+    }
+}
 
-pub (crate) struct LocationLookupTableFrame {
-    raw : Vec<u8>
+/// Largest value of the 24 bits fields of the location lookup table
+const MAX_U24 : u32 = 0xFF_FFFF;
+
+/// MPEG location lookup table (MLLT)
+///
+/// A reference is placed every [LocationLookupTableFrame::frames_between_reference] MPEG frames. The distance
+/// between two references is the distance given in the header plus the deviation of the reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocationLookupTableFrame {
+    frames_between_reference : u16,
+    bytes_between_reference : u32,
+    milliseconds_between_reference : u32,
+    bits_for_bytes_deviation : u8,
+    bits_for_milliseconds_deviation : u8,
+    deviations : Vec<(u32, u32)>
 }
 
 impl RawSize for LocationLookupTableFrame{
     fn raw_size(&self) -> usize {
-        self.raw.len()
+        self.raw_bytes().len()
     }
     fn raw_bytes(&self) -> Vec<u8> {
-        self.raw.clone()
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&self.frames_between_reference.to_be_bytes());
+        bytes.extend_from_slice(&self.bytes_between_reference.to_be_bytes()[1..]);
+        bytes.extend_from_slice(&self.milliseconds_between_reference.to_be_bytes()[1..]);
+        bytes.push(self.bits_for_bytes_deviation);
+        bytes.push(self.bits_for_milliseconds_deviation);
+        // Deviations packed most significant bit first
+        let mut accumulator = 0u64;
+        let mut bit_count = 0;
+        for (bytes_deviation, milliseconds_deviation) in self.deviations.iter() {
+            for (value, bits) in [(*bytes_deviation, self.bits_for_bytes_deviation), (*milliseconds_deviation, self.bits_for_milliseconds_deviation)] {
+                accumulator = (accumulator << bits) | value as u64;
+                bit_count += bits;
+                while bit_count >= 8 {
+                    bit_count -= 8;
+                    bytes.push((accumulator >> bit_count) as u8);
+                }
+            }
+        }
+        if bit_count > 0 {
+            bytes.push((accumulator << (8 - bit_count)) as u8);
+        }
+        bytes
+    }
+}
+
+impl LocationLookupTableFrame {
+    fn from_bytes(data : &[u8]) -> Option<Self> {
+        if data.len() < 10 {
+            return None;
+        }
+        let u24 = |bytes : &[u8]| u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        let bits_for_bytes_deviation = data[8];
+        let bits_for_milliseconds_deviation = data[9];
+        if bits_for_bytes_deviation > 32 || bits_for_milliseconds_deviation > 32 {
+            return None;
+        }
+        let reference_bits = (bits_for_bytes_deviation + bits_for_milliseconds_deviation) as usize;
+        let table = &data[10..];
+        let read_bits = |position : usize, count : u8| {
+            (position..(position + count as usize)).fold(0u32, |value, bit| {
+                let bit = (table[bit / 8] >> (7 - bit % 8)) & 1;
+                value.wrapping_shl(1) | bit as u32
+            })
+        };
+        let references = (table.len() * 8).checked_div(reference_bits).unwrap_or(0);
+        let deviations = (0..references)
+        .map(|i| {
+            let position = i * reference_bits;
+            (read_bits(position, bits_for_bytes_deviation), read_bits(position + bits_for_bytes_deviation as usize, bits_for_milliseconds_deviation))
+        })
+        .collect();
+        Some(Self {
+            frames_between_reference: u16::from_be_bytes([data[0], data[1]]),
+            bytes_between_reference: u24(&data[2..5]),
+            milliseconds_between_reference: u24(&data[5..8]),
+            bits_for_bytes_deviation,
+            bits_for_milliseconds_deviation,
+            deviations
+        })
+    }
+
+    /// Build the lookup table of MPEG frames given as (offset in bytes, duration in milliseconds),
+    /// with a reference every `frames_between_reference` frames
+    ///
+    /// `None` if there are no references or if the distances between the references don't fit in 24 bits
+    pub(crate) fn from_frames(frames : &[(usize, f64)], frames_between_reference : u16) -> Option<Self> {
+        if frames_between_reference == 0 {
+            return None;
+        }
+        let mut elapsed = 0f64;
+        let mut positions = vec![];
+        for (index, (offset, duration)) in frames.iter().enumerate() {
+            if index % frames_between_reference as usize == 0 {
+                positions.push((*offset as u64, elapsed.round() as u64));
+            }
+            elapsed += duration;
+        }
+        let distances = positions.windows(2)
+        .map(|w| (w[1].0 - w[0].0, w[1].1 - w[0].1))
+        .collect::<Vec<(u64, u64)>>();
+        let bytes_between_reference = distances.iter().map(|(bytes, _)| *bytes).min()?;
+        let milliseconds_between_reference = distances.iter().map(|(_, ms)| *ms).min()?;
+        if bytes_between_reference > MAX_U24 as u64 || milliseconds_between_reference > MAX_U24 as u64 {
+            return None;
+        }
+        let deviations = distances.iter()
+        .map(|(bytes, ms)| ((bytes - bytes_between_reference) as u32, (ms - milliseconds_between_reference) as u32))
+        .collect::<Vec<(u32, u32)>>();
+        let bits = |value : u32| (32 - value.leading_zeros()) as u8;
+        let mut bits_for_bytes_deviation = deviations.iter().map(|(bytes, _)| bits(*bytes)).max()?;
+        let mut bits_for_milliseconds_deviation = deviations.iter().map(|(_, ms)| bits(*ms)).max()?;
+        // The size of a reference must be a multiple of 4 bits. Whole bytes, at least one, keep the padding
+        // at the end of the table from being read as another reference
+        let reference_bits = bits_for_bytes_deviation + bits_for_milliseconds_deviation;
+        let padding = if reference_bits == 0 { 8 } else { (8 - reference_bits % 8) % 8 };
+        // The narrower deviation is padded, so that neither exceeds 32 bits
+        if bits_for_bytes_deviation <= bits_for_milliseconds_deviation {
+            bits_for_bytes_deviation += padding
+        } else {
+            bits_for_milliseconds_deviation += padding
+        }
+        Some(Self {
+            frames_between_reference,
+            bytes_between_reference: bytes_between_reference as u32,
+            milliseconds_between_reference: milliseconds_between_reference as u32,
+            bits_for_bytes_deviation,
+            bits_for_milliseconds_deviation,
+            deviations
+        })
+    }
+
+    /// Returns the number of MPEG frames between two references
+    pub fn frames_between_reference(&self) -> u16 {
+        self.frames_between_reference
+    }
+    /// Returns the number of bytes between two references, deviation excluded
+    pub fn bytes_between_reference(&self) -> u32 {
+        self.bytes_between_reference
+    }
+    /// Returns the number of milliseconds between two references, deviation excluded
+    pub fn milliseconds_between_reference(&self) -> u32 {
+        self.milliseconds_between_reference
+    }
+    /// Returns the number of bits used to store the bytes deviations
+    pub fn bits_for_bytes_deviation(&self) -> u8 {
+        self.bits_for_bytes_deviation
+    }
+    /// Returns the number of bits used to store the milliseconds deviations
+    pub fn bits_for_milliseconds_deviation(&self) -> u8 {
+        self.bits_for_milliseconds_deviation
+    }
+    /// Returns the (bytes, milliseconds) deviation of each reference
+    pub fn deviations(&self) -> &Vec<(u32, u32)> {
+        &self.deviations
+    }
+    /// Returns the position of each reference as (offset in bytes, time in milliseconds)
+    /// from the first MPEG frame
+    pub fn references(&self) -> Vec<(u64, u64)> {
+        let mut position = (0u64, 0u64);
+        std::iter::once(position)
+        .chain(self.deviations.iter().map(|(bytes, ms)| {
+            position.0 += (self.bytes_between_reference + bytes) as u64;
+            position.1 += (self.milliseconds_between_reference + ms) as u64;
+            position
+        }))
+        .collect()
+    }
+    /// Returns the last reference at or before `milliseconds`, as (offset in bytes, time in milliseconds)
+    pub fn seek(&self, milliseconds : u64) -> (u64, u64) {
+        self.references()
+        .into_iter()
+        .take_while(|(_, time)| *time <= milliseconds)
+        .last()
+        .unwrap_or((0, 0))
     }
 }
 
-pub(crate) struct  SyncTempoCodeFrame {
+/// Tempo meaning that the following part is beat free
+pub const BEAT_FREE : u16 = 0;
+/// Tempo meaning a single beat stroke followed by a beat free period
+pub const SINGLE_BEAT_STROKE : u16 = 1;
+/// Highest tempo which can be stored
+const MAX_TEMPO : u16 = 510;
+
+/// Synchronised tempo codes (SYTC)
+///
+/// The tempo map is a list of (tempo in BPM, timestamp) sorted by time, the timestamps being expressed in
+/// the unit given by [SyncTempoCodeFrame::time_stamp_format]. The tempos [BEAT_FREE] and [SINGLE_BEAT_STROKE]
+/// start a beat free period.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncTempoCodeFrame {
     time_stamp_format : TimeStampFormat,
-    tempo_data : Vec<u8>
+    tempos : Vec<(u16, u32)>
 }
 impl RawSize for SyncTempoCodeFrame {
     fn raw_size(&self) -> usize {
-        1 + self.tempo_data.len()
+        self.raw_bytes().len()
     }
     fn raw_bytes(&self) -> Vec<u8> {
         let mut bytes = vec![];
         bytes.push(self.time_stamp_format as u8);
-        bytes.append(&mut self.tempo_data.clone());
+        for (tempo, timestamp) in self.tempos.iter() {
+            // The tempos from 255 are stored on 2 bytes, the first one being $FF
+            if *tempo >= 0xFF {
+                bytes.push(0xFF);
+            }
+            bytes.push((*tempo - if *tempo >= 0xFF { 0xFF } else { 0 }) as u8);
+            bytes.extend_from_slice(&timestamp.to_be_bytes());
+        }
         bytes
     }
 }
+impl SyncTempoCodeFrame {
+    /// Create a tempo map, the tempos are sorted by timestamp and capped at 510 BPM
+    pub fn new(time_stamp_format : TimeStampFormat, tempos : Vec<(u16, u32)>) -> Self {
+        let mut frame = Self { time_stamp_format, tempos: vec![] };
+        frame.set_tempos(tempos);
+        frame
+    }
+    /// Returns the unit of the timestamps
+    pub fn time_stamp_format(&self) -> TimeStampFormat {
+        self.time_stamp_format
+    }
+    /// Returns the tempo map as a list of (tempo in BPM, timestamp)
+    pub fn tempos(&self) -> &Vec<(u16, u32)> {
+        &self.tempos
+    }
+    /// Replace the tempo map, the tempos are sorted by timestamp and capped at 510 BPM
+    pub fn set_tempos(&mut self, tempos : Vec<(u16, u32)>) {
+        let mut tempos = tempos.into_iter()
+        .map(|(tempo, timestamp)| (tempo.min(MAX_TEMPO), timestamp))
+        .collect::<Vec<(u16, u32)>>();
+        tempos.sort_by_key(|(_, timestamp)| *timestamp);
+        self.tempos = tempos
+    }
+    /// Returns the tempo in BPM at the time `timestamp`
+    ///
+    /// `None` before the first tempo and during the beat free periods
+    pub fn tempo_at(&self, timestamp : u32) -> Option<u16> {
+        self.tempos.iter()
+        .rev()
+        .find(|(_, t)| *t <= timestamp)
+        .map(|(tempo, _)| *tempo)
+        .filter(|tempo| *tempo > SINGLE_BEAT_STROKE)
+    }
+    /// Returns the average tempo weighted by the duration of each tempo, the beat free periods excluded
+    ///
+    /// The last tempo lasts until `end` if given, otherwise it is only counted if it's the single tempo of the map
+    pub fn average_tempo(&self, end : Option<u32>) -> Option<f64> {
+        let ends = self.tempos.iter()
+        .skip(1)
+        .map(|(_, timestamp)| Some(*timestamp))
+        .chain(std::iter::once(end));
+        let (mut weighted_sum, mut duration) = (0f64, 0f64);
+        for ((tempo, start), end) in self.tempos.iter().zip(ends) {
+            if *tempo <= SINGLE_BEAT_STROKE {
+                continue;
+            }
+            if let Some(end) = end {
+                let length = end.saturating_sub(*start) as f64;
+                weighted_sum += *tempo as f64 * length;
+                duration += length;
+            }
+        }
+        if duration > 0.0 {
+            Some(weighted_sum / duration)
+        } else {
+            // Tempos without duration, all counted equally
+            let tempos = self.tempos.iter()
+            .map(|(tempo, _)| *tempo)
+            .filter(|tempo| *tempo > SINGLE_BEAT_STROKE)
+            .collect::<Vec<u16>>();
+            if tempos.is_empty() {
+                None
+            } else {
+                Some(tempos.iter().map(|tempo| *tempo as f64).sum::<f64>() / tempos.len() as f64)
+            }
+        }
+    }
+}
 pub(crate) struct UnsyncLyricsFrame{
     text_encoding : TextEncoding,
     language : String,
@@ -191,8 +627,8 @@ impl RawSize for UnsyncLyricsFrame {
         let mut bytes = vec![];
         bytes.push(self.text_encoding as u8);
         bytes.append(&mut self.language.clone().into_bytes());
-        bytes.append(&mut self.content_description.to_bytes(&self.text_encoding, true));
-        bytes.append(&mut self.text.to_bytes(&self.text_encoding, false));
+        bytes.append(&mut encode_string(&self.content_description, &self.text_encoding, true));
+        bytes.append(&mut encode_string(&self.text, &self.text_encoding, false));
         bytes
     }
 }
@@ -221,17 +657,108 @@ impl UnsyncLyricsFrame {
         &self.text
     }
 }
-pub(crate) struct SyncLyricsFrame {
-    raw : Vec<u8>
+/// Synchronised lyrics or text (SYLT)
+///
+/// The lyrics are a list of (text, timestamp) sorted by time, the timestamps being expressed in
+/// the unit given by [SyncLyricsFrame::time_stamp_format]. A text beginning with a newline starts a new line.
+#[derive(Debug, Clone)]
+pub struct SyncLyricsFrame {
+    text_encoding : TextEncoding,
+    language : String,
+    time_stamp_format : TimeStampFormat,
+    content_type : TextContent,
+    content_description : String,
+    lyrics : Vec<(String, u32)>
 }
 
 impl RawSize for SyncLyricsFrame {
     fn raw_size(&self) -> usize {
-        self.raw.len()
+        self.raw_bytes().len()
     }
 
     fn raw_bytes(&self) -> Vec<u8> {
-        self.raw.clone()
+        let mut bytes = vec![];
+        bytes.push(self.text_encoding as u8);
+        bytes.append(&mut self.language.clone().into_bytes());
+        bytes.push(self.time_stamp_format as u8);
+        bytes.push(self.content_type as u8);
+        bytes.append(&mut encode_string(&self.content_description, &self.text_encoding, true));
+        for (text, timestamp) in self.lyrics.iter() {
+            bytes.append(&mut encode_string(text, &self.text_encoding, true));
+            bytes.extend_from_slice(&timestamp.to_be_bytes());
+        }
+        bytes
+    }
+}
+impl SyncLyricsFrame {
+    /// Create synchronised lyrics
+    ///
+    /// `language` is an ISO-639-2 code, `lyrics` are sorted by timestamp
+    pub fn new(language : &str, time_stamp_format : TimeStampFormat, content_type : TextContent, description : &str, lyrics : Vec<(String, u32)>) -> Self {
+        let mut frame = Self {
+            text_encoding: TextEncoding::Iso8859_1,
+            language: language.into(),
+            time_stamp_format,
+            content_type,
+            content_description: description.into(),
+            lyrics: vec![]
+        };
+        frame.set_lyrics(lyrics);
+        frame
+    }
+
+    /// Create synchronised lyrics, timestamped in milliseconds, from the content of a LRC file
+    pub fn from_lrc(lrc : &str, language : &str, description : &str) -> Self {
+        Self::new(language, TimeStampFormat::Milliseconds, TextContent::Lyrics, description, parse_lrc(lrc))
+    }
+
+    /// Returns the lyrics in the LRC format
+    ///
+    /// `None` if the timestamps aren't expressed in milliseconds
+    pub fn to_lrc(&self) -> Option<String> {
+        match self.time_stamp_format {
+            TimeStampFormat::Milliseconds => Some(format_lrc(&self.lyrics)),
+            TimeStampFormat::MPEGFrames => None
+        }
+    }
+
+    fn update_text_encoding(&mut self) {
+        self.text_encoding = if self.content_description.is_ascii() && self.lyrics.iter().all(|(text, _)| text.is_ascii()) {
+            TextEncoding::Iso8859_1
+        } else {
+            TextEncoding::UnicodeUtf16
+        }
+    }
+
+    /// Returns the language of the lyrics
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+    /// Returns the unit of the timestamps
+    pub fn time_stamp_format(&self) -> TimeStampFormat {
+        self.time_stamp_format
+    }
+    /// Returns the content type
+    pub fn content_type(&self) -> TextContent {
+        self.content_type
+    }
+    /// Set the content type
+    pub fn set_content_type(&mut self, content_type : TextContent) {
+        self.content_type = content_type
+    }
+    /// Returns the content description
+    pub fn description(&self) -> &str {
+        &self.content_description
+    }
+    /// Returns the lyrics as a list of (text, timestamp)
+    pub fn lyrics(&self) -> &Vec<(String, u32)> {
+        &self.lyrics
+    }
+    /// Replace the lyrics, which are sorted by timestamp
+    pub fn set_lyrics(&mut self, mut lyrics : Vec<(String, u32)>) {
+        lyrics.sort_by_key(|(_, timestamp)| *timestamp);
+        self.lyrics = lyrics;
+        self.update_text_encoding()
     }
 }
 
@@ -250,8 +777,8 @@ impl RawSize for CommentFrame{
         let mut bytes = vec![];
         bytes.push(self.text_encoding as u8);
         bytes.append(&mut self.language.clone().into_bytes());
-        bytes.append(&mut self.content_description.to_bytes(&self.text_encoding, true));
-        bytes.append(&mut self.text.to_bytes(&self.text_encoding, false));
+        bytes.append(&mut encode_string(&self.content_description, &self.text_encoding, true));
+        bytes.append(&mut encode_string(&self.text, &self.text_encoding, false));
         //bytes.push(NULL_TERMINATE);
         // bytes.push(NULL_TERMINATE);
         bytes
@@ -281,37 +808,436 @@ impl CommentFrame {
      }
 }
 
-pub(crate) struct RelativeVolumeAdjustementFrame {
-    raw : Vec<u8>
+/// Volume adjustment of a channel
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChannelAdjustment {
+    pub channel : ChannelType,
+    /// Volume adjustment in decibels
+    pub adjustment : f32,
+    /// Peak volume, 1.0 being the full scale
+    pub peak : Option<f64>
+}
+
+/// Bits used for the volume changes and the peaks written in a RVAD frame
+const RVAD_BITS : u8 = 16;
+/// Number of bits of the adjustments of the EQUA frames converted from EQU2
+const EQUA_BITS : u8 = 16;
+/// Channels of a RVAD frame, in the order of the increment/decrement flags
+const RVAD_CHANNELS : [ChannelType; 6] = [
+    ChannelType::FrontRight, ChannelType::FrontLeft, ChannelType::BackRight,
+    ChannelType::BackLeft, ChannelType::FrontCentre, ChannelType::Subwoofer
+];
+
+/// Read a big endian unsigned integer, the bytes beyond 64 bits being ignored
+fn read_unsigned(bytes : &[u8]) -> u64 {
+    bytes.iter().take(8).fold(0u64, |value, byte| (value << 8) | *byte as u64)
+}
+
+fn peak_from_raw(value : u64, bits : u8) -> f64 {
+    value as f64 / 2f64.powi(bits as i32 - 1)
+}
+
+fn peak_to_raw(peak : f64, bits : u8) -> u64 {
+    (peak * 2f64.powi(bits as i32 - 1)).round().clamp(0.0, (2f64.powi(bits as i32)) - 1.0) as u64
+}
+
+/// Relative volume adjustment, RVAD (ID3v2.3) or RVA2 (ID3v2.4)
+///
+/// The RVA2 frames are identified by a string, `"track"` and `"album"` being used for the ReplayGain values.
+/// A RVAD frame has no identification, and stores its volume changes as a fraction of the full scale.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelativeVolumeAdjustementFrame {
+    identification : Option<String>,
+    channels : Vec<ChannelAdjustment>
 }
 impl RawSize for RelativeVolumeAdjustementFrame{
     fn raw_size(&self) -> usize {
-        self.raw.len()
+        self.raw_bytes().len()
     }
     fn raw_bytes(&self) -> Vec<u8> {
-        self.raw.clone()
+        match &self.identification {
+            Some(identification) => self.rva2_bytes(identification),
+            None => self.rvad_bytes()
+        }
     }
 }
-pub(crate) struct EqualiisationFrame {
-    raw : Vec<u8>
+impl RelativeVolumeAdjustementFrame {
+    /// Create a RVA2 frame identified by `identification`
+    pub fn new(identification : &str, channels : Vec<ChannelAdjustment>) -> Self {
+        Self { identification: Some(identification.into()), channels }
+    }
+
+    /// Create a RVAD frame
+    pub fn new_v23(channels : Vec<ChannelAdjustment>) -> Self {
+        Self { identification: None, channels }
+    }
+
+    fn from_rva2_bytes(mut data : Vec<u8>) -> Option<Self> {
+        let identification = data.take_string(&TextEncoding::Iso8859_1)?;
+        let mut channels = vec![];
+        while !data.is_empty() {
+            let header = data.get(0..4)?;
+            let channel = ChannelType::from_raw_value(header[0]).unwrap_or(ChannelType::Other);
+            let adjustment = i16::from_be_bytes([header[1], header[2]]) as f32 / 512.0;
+            let bits = header[3];
+            let peak_len = (bits as usize).div_ceil(8);
+            let peak = read_unsigned(data.get(4..(4 + peak_len))?);
+            data.drain(0..(4 + peak_len));
+            channels.push(ChannelAdjustment {
+                channel,
+                adjustment,
+                peak: if bits == 0 { None } else { Some(peak_from_raw(peak, bits)) }
+            });
+        }
+        Some(Self { identification: Some(identification), channels })
+    }
+
+    fn from_rvad_bytes(data : Vec<u8>) -> Option<Self> {
+        let flags = *data.first()?;
+        let bits = *data.get(1)?;
+        if bits == 0 {
+            return None;
+        }
+        let field_len = (bits as usize).div_ceil(8);
+        let fields = data[2..].chunks_exact(field_len).map(read_unsigned).collect::<Vec<u64>>();
+        let full_scale = (2f64.powi(bits as i32) - 1.0).max(1.0);
+        let adjustment = |value : u64, increment : bool| {
+            let fraction = value as f64 / full_scale;
+            let factor = if increment { 1.0 + fraction } else { 1.0 - fraction };
+            (20.0 * factor.max(1e-5).log10()) as f32
+        };
+        // Volume changes of the (right, left) and (right back, left back) pairs are followed by their peaks,
+        // the centre and the bass by their peak
+        let layout : [(usize, usize); 6] = [(0, 2), (1, 3), (4, 6), (5, 7), (8, 9), (10, 11)];
+        let channels = RVAD_CHANNELS.iter()
+        .zip(layout.iter())
+        .enumerate()
+        .filter_map(|(index, (channel, (change, peak)))| {
+            Some(ChannelAdjustment {
+                channel: *channel,
+                adjustment: adjustment(*fields.get(*change)?, flags & (1 << index) != 0),
+                peak: Some(peak_from_raw(*fields.get(*peak)?, bits))
+            })
+        })
+        .collect();
+        Some(Self { identification: None, channels })
+    }
+
+    fn rva2_bytes(&self, identification : &str) -> Vec<u8> {
+        let mut bytes = identification.to_string().to_bytes(&TextEncoding::Iso8859_1, true);
+        for channel in self.channels.iter() {
+            bytes.push(channel.channel as u8);
+            let adjustment = (channel.adjustment * 512.0).round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+            bytes.extend_from_slice(&adjustment.to_be_bytes());
+            match channel.peak {
+                Some(peak) => {
+                    bytes.push(16);
+                    bytes.extend_from_slice(&(peak_to_raw(peak, 16) as u16).to_be_bytes());
+                }
+                None => bytes.push(0)
+            }
+        }
+        bytes
+    }
+
+    fn rvad_bytes(&self) -> Vec<u8> {
+        let full_scale = 2f64.powi(RVAD_BITS as i32) - 1.0;
+        // The master volume applies to the front channels which aren't given
+        let master = self.channel(ChannelType::MasterVolume);
+        let channels = RVAD_CHANNELS.iter()
+        .map(|channel| match (self.channel(*channel), channel) {
+            (Some(adjustment), _) => Some(adjustment),
+            (None, ChannelType::FrontRight) | (None, ChannelType::FrontLeft) => master,
+            _ => None
+        })
+        .collect::<Vec<Option<&ChannelAdjustment>>>();
+        let mut flags = 0u8;
+        let mut changes = vec![];
+        let mut peaks = vec![];
+        for (index, channel) in channels.iter().enumerate() {
+            let (adjustment, peak) = channel.map_or((0.0, None), |c| (c.adjustment as f64, c.peak));
+            let factor = 10f64.powf(adjustment / 20.0);
+            if adjustment >= 0.0 {
+                flags |= 1 << index;
+            }
+            changes.push(((factor - 1.0).abs() * full_scale).round().min(full_scale) as u16);
+            peaks.push(peak_to_raw(peak.unwrap_or(0.0), RVAD_BITS) as u16);
+        }
+        // The optional groups are written up to the last one used
+        let group_count = if channels[5].is_some() { 4 } else if channels[4].is_some() { 3 } else if channels[2].is_some() || channels[3].is_some() { 2 } else { 1 };
+        let mut fields = vec![changes[0], changes[1], peaks[0], peaks[1]];
+        if group_count >= 2 {
+            fields.extend_from_slice(&[changes[2], changes[3], peaks[2], peaks[3]]);
+        }
+        if group_count >= 3 {
+            fields.extend_from_slice(&[changes[4], peaks[4]]);
+        }
+        if group_count >= 4 {
+            fields.extend_from_slice(&[changes[5], peaks[5]]);
+        }
+        let mut bytes = vec![flags, RVAD_BITS];
+        for field in fields {
+            bytes.extend_from_slice(&field.to_be_bytes());
+        }
+        bytes
+    }
+
+    /// Returns the identification of a RVA2 frame, `None` for a RVAD frame
+    pub fn identification(&self) -> Option<&str> {
+        self.identification.as_deref()
+    }
+    /// Returns the adjustments of the channels
+    pub fn channels(&self) -> &Vec<ChannelAdjustment> {
+        &self.channels
+    }
+    /// Returns the adjustment of `channel`
+    pub fn channel(&self, channel : ChannelType) -> Option<&ChannelAdjustment> {
+        self.channels.iter().find(|c| c.channel == channel)
+    }
+    /// Replace the adjustments of the channels
+    pub fn set_channels(&mut self, channels : Vec<ChannelAdjustment>) {
+        self.channels = channels
+    }
+    /// Returns the volume adjustment and the peak of the whole track : the master volume,
+    /// or else the average of the front channels
+    pub fn master(&self) -> Option<(f32, Option<f64>)> {
+        if let Some(master) = self.channel(ChannelType::MasterVolume) {
+            return Some((master.adjustment, master.peak));
+        }
+        let front = [ChannelType::FrontLeft, ChannelType::FrontRight].iter()
+        .filter_map(|channel| self.channel(*channel))
+        .collect::<Vec<&ChannelAdjustment>>();
+        if front.is_empty() {
+            return None;
+        }
+        let adjustment = front.iter().map(|c| c.adjustment).sum::<f32>() / front.len() as f32;
+        let peak = front.iter().filter_map(|c| c.peak).reduce(f64::max);
+        Some((adjustment, peak))
+    }
+    pub(crate) fn set_identification(&mut self, identification : Option<String>) {
+        self.identification = identification
+    }
+}
+/// Equalisation (EQUA, ID3v2.3)
+///
+/// The bands are a list of (frequency in Hz, adjustment) sorted by frequency. The adjustments are stored on
+/// [EqualiisationFrame::adjustment_bits] bits, a negative adjustment being a decrement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EqualiisationFrame {
+    adjustment_bits : u8,
+    bands : Vec<(u16, i64)>
 }
 impl RawSize for EqualiisationFrame{
     fn raw_size(&self) -> usize {
-        self.raw.len()
+        self.raw_bytes().len()
     }
     fn raw_bytes(&self) -> Vec<u8> {
-        self.raw.clone()
+        let mut bytes = vec![self.adjustment_bits];
+        let adjustment_len = (self.adjustment_bits as usize).div_ceil(8);
+        for (frequency, adjustment) in self.bands.iter() {
+            // The most significant bit of the frequency is set for an increment
+            let increment = if *adjustment >= 0 { 0x8000 } else { 0 };
+            bytes.extend_from_slice(&(increment | (frequency & 0x7FFF)).to_be_bytes());
+            bytes.extend_from_slice(&adjustment.unsigned_abs().to_be_bytes()[(8 - adjustment_len)..]);
+        }
+        bytes
     }
 }
-pub(crate) struct ReverbFrame {
-    raw : Vec<u8>
+impl EqualiisationFrame {
+    /// Create an equalisation with adjustments stored on `adjustment_bits` bits (1 to 32),
+    /// the bands are sorted by frequency
+    pub fn new(adjustment_bits : u8, bands : Vec<(u16, i64)>) -> Self {
+        let mut frame = Self { adjustment_bits: adjustment_bits.clamp(1, 32), bands: vec![] };
+        frame.set_bands(bands);
+        frame
+    }
+    fn from_bytes(data : &[u8]) -> Option<Self> {
+        let adjustment_bits = *data.first()?;
+        if adjustment_bits == 0 || adjustment_bits > 32 {
+            return None;
+        }
+        let adjustment_len = (adjustment_bits as usize).div_ceil(8);
+        let bands = data[1..].chunks_exact(2 + adjustment_len)
+        .map(|band| {
+            let frequency = u16::from_be_bytes([band[0], band[1]]);
+            let adjustment = read_unsigned(&band[2..]) as i64;
+            (frequency & 0x7FFF, if frequency & 0x8000 != 0 { adjustment } else { -adjustment })
+        })
+        .collect();
+        Some(Self { adjustment_bits, bands })
+    }
+    /// Returns the number of bits of the adjustments
+    pub fn adjustment_bits(&self) -> u8 {
+        self.adjustment_bits
+    }
+    /// Returns the bands as a list of (frequency in Hz, adjustment)
+    pub fn bands(&self) -> &Vec<(u16, i64)> {
+        &self.bands
+    }
+    /// Replace the bands, which are sorted by frequency
+    ///
+    /// The frequencies are capped at 32767 Hz and the adjustments at the range of [EqualiisationFrame::adjustment_bits]
+    pub fn set_bands(&mut self, bands : Vec<(u16, i64)>) {
+        let max_adjustment = ((1u64 << self.adjustment_bits) - 1) as i64;
+        let mut bands = bands.into_iter()
+        .map(|(frequency, adjustment)| (frequency.min(0x7FFF), adjustment.clamp(-max_adjustment, max_adjustment)))
+        .collect::<Vec<(u16, i64)>>();
+        bands.sort_by_key(|(frequency, _)| *frequency);
+        self.bands = bands
+    }
+    /// Convert the bands to EQU2 points without identification, the adjustments being
+    /// fractions of the full scale of [EqualiisationFrame::adjustment_bits] as the RVAD volume changes
+    pub(crate) fn to_equalisation2(&self) -> Equalisation2Frame {
+        let full_scale = ((1u64 << self.adjustment_bits) - 1).max(1) as f64;
+        let points = self.bands.iter()
+        .map(|(frequency, adjustment)| {
+            let fraction = adjustment.unsigned_abs() as f64 / full_scale;
+            let factor = if *adjustment >= 0 { 1.0 + fraction } else { 1.0 - fraction };
+            (*frequency as f32, (20.0 * factor.max(1e-5).log10()) as f32)
+        })
+        .collect();
+        Equalisation2Frame::new(InterpolationMethod::Band, "", points)
+    }
+    /// Convert the points of an EQU2 frame to bands, the adjustments beyond the full scale being capped
+    pub(crate) fn from_equalisation2(equalisation : &Equalisation2Frame) -> Self {
+        let full_scale = ((1u64 << EQUA_BITS) - 1) as f64;
+        let bands = equalisation.points.iter()
+        .map(|(frequency, adjustment)| {
+            let factor = 10f64.powf(*adjustment as f64 / 20.0);
+            let change = ((factor - 1.0).abs() * full_scale).round().min(full_scale) as i64;
+            (frequency.round() as u16, if *adjustment >= 0.0 { change } else { -change })
+        })
+        .collect();
+        Self::new(EQUA_BITS, bands)
+    }
+}
+
+/// Equalisation (EQU2, ID3v2.4)
+///
+/// The adjustment points are a list of (frequency in Hz, adjustment in decibels) sorted by frequency,
+/// the frequencies having a precision of 1/2 Hz and the adjustments of 1/512 dB.
+/// Several EQU2 frames can be stored, with different identifications.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equalisation2Frame {
+    interpolation : InterpolationMethod,
+    identification : String,
+    points : Vec<(f32, f32)>
+}
+impl RawSize for Equalisation2Frame{
+    fn raw_size(&self) -> usize {
+        self.raw_bytes().len()
+    }
+    fn raw_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![self.interpolation as u8];
+        bytes.append(&mut self.identification.to_bytes(&TextEncoding::Iso8859_1, true));
+        for (frequency, adjustment) in self.points.iter() {
+            bytes.extend_from_slice(&((frequency * 2.0).round() as u16).to_be_bytes());
+            bytes.extend_from_slice(&((adjustment * 512.0).round() as i16).to_be_bytes());
+        }
+        bytes
+    }
+}
+impl Equalisation2Frame {
+    /// Create an equalisation identified by `identification`, the points are sorted by frequency
+    pub fn new(interpolation : InterpolationMethod, identification : &str, points : Vec<(f32, f32)>) -> Self {
+        let mut frame = Self { interpolation, identification: identification.into(), points: vec![] };
+        frame.set_points(points);
+        frame
+    }
+    fn from_bytes(mut data : Vec<u8>) -> Option<Self> {
+        let interpolation = InterpolationMethod::from_raw_value(*data.first()?)?;
+        data.remove(0);
+        let identification = data.take_string(&TextEncoding::Iso8859_1)?;
+        let points = data.chunks_exact(4)
+        .map(|point| (
+            u16::from_be_bytes([point[0], point[1]]) as f32 / 2.0,
+            i16::from_be_bytes([point[2], point[3]]) as f32 / 512.0
+        ))
+        .collect();
+        Some(Self { interpolation, identification, points })
+    }
+    /// Returns the interpolation method
+    pub fn interpolation(&self) -> InterpolationMethod {
+        self.interpolation
+    }
+    /// Set the interpolation method
+    pub fn set_interpolation(&mut self, interpolation : InterpolationMethod) {
+        self.interpolation = interpolation
+    }
+    /// Returns the identification of the equalisation
+    pub fn identification(&self) -> &str {
+        &self.identification
+    }
+    /// Returns the adjustment points as a list of (frequency in Hz, adjustment in decibels)
+    pub fn points(&self) -> &Vec<(f32, f32)> {
+        &self.points
+    }
+    /// Replace the adjustment points, which are sorted by frequency
+    ///
+    /// The frequencies are capped at 32767.5 Hz and the adjustments at ±64 dB, a frequency given twice keeps its last adjustment
+    pub fn set_points(&mut self, points : Vec<(f32, f32)>) {
+        let mut points = points.into_iter()
+        .map(|(frequency, adjustment)| (frequency.clamp(0.0, 32767.5), adjustment.clamp(-64.0, 63.998)))
+        .collect::<Vec<(f32, f32)>>();
+        points.reverse();
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        points.dedup_by(|a, b| (a.0 * 2.0).round() == (b.0 * 2.0).round());
+        self.points = points
+    }
+}
+
+/// Reverb (RVRB)
+///
+/// The feedbacks and premixes are amounts from 0 to 255 (100%)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ReverbFrame {
+    /// Delay between the bounces of the left channel in milliseconds
+    pub reverb_left : u16,
+    /// Delay between the bounces of the right channel in milliseconds
+    pub reverb_right : u16,
+    /// Number of bounces of the left channel, 255 for infinite
+    pub bounces_left : u8,
+    /// Number of bounces of the right channel, 255 for infinite
+    pub bounces_right : u8,
+    pub feedback_left_to_left : u8,
+    pub feedback_left_to_right : u8,
+    pub feedback_right_to_right : u8,
+    pub feedback_right_to_left : u8,
+    pub premix_left_to_right : u8,
+    pub premix_right_to_left : u8
 }
 impl RawSize for ReverbFrame{
     fn raw_size(&self) -> usize {
-        self.raw.len()
+        12
     }
     fn raw_bytes(&self) -> Vec<u8> {
-        self.raw.clone()
+        let mut bytes = vec![];
+        bytes.extend_from_slice(&self.reverb_left.to_be_bytes());
+        bytes.extend_from_slice(&self.reverb_right.to_be_bytes());
+        bytes.extend_from_slice(&[
+            self.bounces_left, self.bounces_right,
+            self.feedback_left_to_left, self.feedback_left_to_right,
+            self.feedback_right_to_right, self.feedback_right_to_left,
+            self.premix_left_to_right, self.premix_right_to_left
+        ]);
+        bytes
+    }
+}
+impl ReverbFrame {
+    fn from_bytes(data : &[u8]) -> Option<Self> {
+        let data = data.get(0..12)?;
+        Some(Self {
+            reverb_left: u16::from_be_bytes([data[0], data[1]]),
+            reverb_right: u16::from_be_bytes([data[2], data[3]]),
+            bounces_left: data[4],
+            bounces_right: data[5],
+            feedback_left_to_left: data[6],
+            feedback_left_to_right: data[7],
+            feedback_right_to_right: data[8],
+            feedback_right_to_left: data[9],
+            premix_left_to_right: data[10],
+            premix_right_to_left: data[11]
+        })
     }
 }
 
@@ -364,11 +1290,15 @@ impl AttachedPictureFrame {
     }
 }
 
-pub(crate) struct GeneralEncapsulatedObjectFrame {
+/// General encapsulated object (GEOB)
+///
+/// An arbitrary file stored in the tag, identified by its content description
+#[derive(Debug, Clone)]
+pub struct GeneralEncapsulatedObjectFrame {
     text_encoding : TextEncoding,
     mime_type : String,
     filename : String,
-    _content : Vec<u8>,
+    content_description : String,
     encapsulated_object : Vec<u8>
 }
 impl RawSize for GeneralEncapsulatedObjectFrame{
@@ -379,36 +1309,148 @@ impl RawSize for GeneralEncapsulatedObjectFrame{
         let mut bytes = vec![];
         bytes.push(self.text_encoding as u8);
         bytes.append(&mut self.mime_type.to_bytes(&TextEncoding::Iso8859_1, true));
-        bytes.append(&mut self.filename.to_bytes(&self.text_encoding, true));
-        bytes.push(NULL_TERMINATE);
-        if self.text_encoding.is_one_byte(){
-            bytes.push(NULL_TERMINATE);
-        }
+        bytes.append(&mut encode_string(&self.filename, &self.text_encoding, true));
+        bytes.append(&mut encode_string(&self.content_description, &self.text_encoding, true));
         bytes.append(&mut self.encapsulated_object.clone());
         bytes
     }
 }
+impl GeneralEncapsulatedObjectFrame {
+    /// Create an encapsulated object
+    ///
+    /// `filename` is the name of the original file, it may be empty
+    pub fn new(mime_type : &str, filename : &str, description : &str, data : Vec<u8>) -> Self {
+        let text_encoding = if filename.is_ascii() && description.is_ascii() {
+            TextEncoding::Iso8859_1
+        } else {
+            TextEncoding::UnicodeUtf16
+        };
+        Self {
+            text_encoding,
+            mime_type: mime_type.into(),
+            filename: filename.into(),
+            content_description: description.into(),
+            encapsulated_object: data
+        }
+    }
+    fn from_bytes(mut data : Vec<u8>) -> Option<Self> {
+        let text_encoding = TextEncoding::from_raw_value(*data.first()?).unwrap_or(TextEncoding::Iso8859_1);
+        data.remove(0);
+        let mime_type = data.take_string(&TextEncoding::Iso8859_1)?;
+        let filename = data.take_string(&text_encoding)?;
+        let content_description = data.take_string(&text_encoding)?;
+        Some(Self {
+            text_encoding,
+            mime_type,
+            filename,
+            content_description,
+            encapsulated_object: data
+        })
+    }
+    /// Returns the MIME type of the object
+    pub fn mime_type(&self) -> &str {
+        &self.mime_type
+    }
+    /// Returns the name of the file the object comes from, it may be empty
+    pub fn filename(&self) -> &str {
+        &self.filename
+    }
+    /// Returns the content description, which identifies the object in the tag
+    pub fn description(&self) -> &str {
+        &self.content_description
+    }
+    /// Returns the content of the object
+    pub fn data(&self) -> &Vec<u8> {
+        &self.encapsulated_object
+    }
+    /// Replace the content of the object
+    pub fn set_data(&mut self, data : Vec<u8>) {
+        self.encapsulated_object = data
+    }
+}
+
+/// Decode a big endian counter of any width, saturating beyond 64 bits
+fn counter_from_bytes(bytes : &[u8]) -> u64 {
+    bytes.iter().fold(0u64, |counter, byte| {
+        counter.checked_mul(256).map_or(u64::MAX, |counter| counter | *byte as u64)
+    })
+}
+
+/// Encode a counter on the smallest big endian width, at least 4 bytes
+fn counter_to_bytes(counter : u64) -> Vec<u8> {
+    let bytes = counter.to_be_bytes();
+    let start = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len()).min(4);
+    bytes[start..].to_vec()
+}
 
 pub(crate) struct PlayCounterFrame {
-    raw : Vec<u8>
+    counter : u64
 }
 impl RawSize for PlayCounterFrame{
     fn raw_size(&self) -> usize {
-        self.raw.len()
+        self.raw_bytes().len()
     }
     fn raw_bytes(&self) -> Vec<u8> {
-        self.raw.clone()
+        counter_to_bytes(self.counter)
     }
 }
-pub(crate) struct PopularimeterFrame {
-    raw : Vec<u8>
+impl PlayCounterFrame {
+    pub(crate) fn new(counter : u64) -> Self {
+        Self { counter }
+    }
+    pub(crate) fn counter(&self) -> u64 {
+        self.counter
+    }
+    pub(crate) fn set_counter(&mut self, counter : u64) {
+        self.counter = counter
+    }
+}
+
+/// Rating and play counter of a user (POPM)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PopularimeterFrame {
+    email : String,
+    rating : u8,
+    counter : Option<u64>
 }
 impl RawSize for PopularimeterFrame{
     fn raw_size(&self) -> usize {
-        self.raw.len()
+        self.raw_bytes().len()
     }
     fn raw_bytes(&self) -> Vec<u8> {
-        self.raw.clone()
+        let mut bytes = vec![];
+        bytes.append(&mut self.email.to_bytes(&TextEncoding::Iso8859_1, true));
+        bytes.push(self.rating);
+        if let Some(counter) = self.counter {
+            bytes.append(&mut counter_to_bytes(counter));
+        }
+        bytes
+    }
+}
+impl PopularimeterFrame {
+    /// Create a popularimeter for the user identified by `email`
+    pub fn new(email : &str, rating : u8, counter : Option<u64>) -> Self {
+        Self { email: email.into(), rating, counter }
+    }
+    /// Returns the email identifying the user
+    pub fn email(&self) -> &str {
+        &self.email
+    }
+    /// Returns the rating, from 1 (worst) to 255 (best), 0 if unknown
+    pub fn rating(&self) -> u8 {
+        self.rating
+    }
+    /// Set the rating
+    pub fn set_rating(&mut self, rating : u8) {
+        self.rating = rating
+    }
+    /// Returns the number of times the user played the file
+    pub fn counter(&self) -> Option<u64> {
+        self.counter
+    }
+    /// Set the number of times the user played the file, `None` to omit the counter
+    pub fn set_counter(&mut self, counter : Option<u64>) {
+        self.counter = counter
     }
 }
 pub(crate) struct RecommendedBufferSizeFrame {
@@ -449,28 +1491,82 @@ impl RawSize for AudioEncryptionFrame {
     }
 
 }
-pub (crate) struct LinkedInfoFrame {
-    raw : Vec<u8>
+/// Linked information (LINK)
+///
+/// Points at a frame stored in another file, the ID data identifying it among the frames
+/// of the same kind (e.g. the language and the description of a comment)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkedInfoFrame {
+    frame_id : String,
+    url : String,
+    id_data : String
 }
 impl RawSize for LinkedInfoFrame{
     fn raw_size(&self) -> usize {
-        self.raw.len()
+        self.raw_bytes().len()
     }
     fn raw_bytes(&self) -> Vec<u8> {
-        self.raw.clone()
+        let mut bytes = self.frame_id.clone().into_bytes();
+        bytes.append(&mut self.url.to_bytes(&TextEncoding::Iso8859_1, true));
+        bytes.append(&mut self.id_data.to_bytes(&TextEncoding::Iso8859_1, false));
+        bytes
+    }
+}
+impl LinkedInfoFrame {
+    /// Create a link to the frame `frame_id` of the file at `url`
+    ///
+    /// `None` if `frame_id` isn't made of 4 uppercase letters or digits
+    pub fn new(frame_id : &str, url : &str, id_data : &str) -> Option<Self> {
+        if frame_id.len() != 4 || !frame_id.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()) {
+            return None;
+        }
+        Some(Self { frame_id: frame_id.into(), url: url.into(), id_data: id_data.into() })
+    }
+    fn from_bytes(mut data : Vec<u8>) -> Option<Self> {
+        let frame_id = String::from_utf8(data.drain(0..4.min(data.len())).collect()).ok()?;
+        let url = data.take_string(&TextEncoding::Iso8859_1)?;
+        let id_data = data.take_string(&TextEncoding::Iso8859_1)?;
+        Some(Self { frame_id, url, id_data })
+    }
+    /// Returns the identifier of the linked frame
+    pub fn frame_id(&self) -> &str {
+        &self.frame_id
+    }
+    /// Returns the URL of the file containing the linked frame
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+    /// Returns the data identifying the linked frame, empty if not needed
+    pub fn id_data(&self) -> &str {
+        &self.id_data
     }
 }
 
-pub(crate) struct PositionSyncFrame {
-    raw : Vec<u8>
+/// Position synchronisation (POSS)
+///
+/// The position in the audio from which the file starts, e.g. to resume a stream
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PositionSyncFrame {
+    pub time_stamp_format : TimeStampFormat,
+    /// Position in the unit given by [PositionSyncFrame::time_stamp_format]
+    pub position : u32
 }
 
 impl RawSize for PositionSyncFrame{
     fn raw_size(&self) -> usize {
-        self.raw.len()
+        5
     }
     fn raw_bytes(&self) -> Vec<u8> {
-        self.raw.clone()
+        let mut bytes = vec![self.time_stamp_format as u8];
+        bytes.extend_from_slice(&self.position.to_be_bytes());
+        bytes
+    }
+}
+impl PositionSyncFrame {
+    fn from_bytes(data : &[u8]) -> Option<Self> {
+        let time_stamp_format = TimeStampFormat::from_raw_value(*data.first()?)?;
+        let position = counter_from_bytes(&data[1..]).min(u32::MAX as u64) as u32;
+        Some(Self { time_stamp_format, position })
     }
 }
 pub(crate) struct TermsUseFrame {
@@ -480,17 +1576,13 @@ pub(crate) struct TermsUseFrame {
 }
 impl RawSize for TermsUseFrame {
     fn raw_size(&self) -> usize {
-        1 + 3 + if self.text_encoding.is_one_byte() {
-            self.text.len() 
-        } else {
-            self.text.len() * 2
-        }
+        self.raw_bytes().len()
     }
     fn raw_bytes(&self) -> Vec<u8> {
         let mut bytes = vec![];
         bytes.push(self.text_encoding as u8);
         bytes.append(&mut self.language.clone().into_bytes());
-        bytes.append(&mut self.text.to_bytes(&self.text_encoding, false));
+        bytes.append(&mut encode_string(&self.text, &self.text_encoding, false));
         bytes
     }
 }
@@ -544,14 +1636,18 @@ impl RawSize for GroupIdentificationRegistationFrame{
     }
 }
 
-pub(crate) struct PrivateFrame {
+/// Private frame (PRIV)
+///
+/// Binary data identified by its owner, several frames can have the same owner
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrivateFrame {
     owner_id : String,
     private_data : Vec<u8>
 }
 
 impl RawSize for PrivateFrame {
     fn raw_size(&self) -> usize {
-        self.owner_id.len() + 1 /*null terminated bytes*/ + self.private_data.len()
+        self.raw_bytes().len()
     }
     fn raw_bytes(&self) -> Vec<u8> {
         let mut bytes = vec![];
@@ -561,11 +1657,43 @@ impl RawSize for PrivateFrame {
     }
 }
 
+impl PrivateFrame {
+    /// Create a private frame, `owner_id` is usually an URL or an email
+    pub fn new(owner_id : &str, data : Vec<u8>) -> Self {
+        Self { owner_id: owner_id.into(), private_data: data }
+    }
+    /// Create a private frame from the content of a known owner
+    ///
+    /// `None` if the content holds a malformed GUID
+    pub fn from_content(content : &PrivateContent) -> Option<Self> {
+        Some(Self::new(content.owner_id(), content.to_bytes()?))
+    }
+    /// Returns the owner identifier
+    pub fn owner_id(&self) -> &str {
+        &self.owner_id
+    }
+    /// Returns the private data
+    pub fn data(&self) -> &Vec<u8> {
+        &self.private_data
+    }
+    /// Replace the private data
+    pub fn set_data(&mut self, data : Vec<u8>) {
+        self.private_data = data
+    }
+    /// Decode the private data if the owner is known
+    ///
+    /// `None` if the owner is unknown or the data is malformed
+    pub fn content(&self) -> Option<PrivateContent> {
+        PrivateContent::from_bytes(&self.owner_id, &self.private_data)
+    }
+}
+
 pub(crate) enum FrameValue {
     UFIF(UniqueFileIdentifierFrame),
     TF(TextFrame),
     UIF(UserInfoFrame),
     UF(UrlFrame),
+    UUF(UserUrlFrame),
     IPF(InvolvedPeopleFrame),
     MCIF(MusicCdIdframe),
     ETF(EventTimingFrame),
@@ -576,6 +1704,7 @@ pub(crate) enum FrameValue {
     CF(CommentFrame),
     RVAF(RelativeVolumeAdjustementFrame),
     EF(EqualiisationFrame),
+    E2F(Equalisation2Frame),
     RF(ReverbFrame),
     APF(AttachedPictureFrame),
     GEOF(GeneralEncapsulatedObjectFrame),
@@ -592,12 +1721,26 @@ pub(crate) enum FrameValue {
     GIRF(GroupIdentificationRegistationFrame),
     PrivF(PrivateFrame),
     Undefined(Vec<u8>),
-    ICFF(i16),
-    NoValue
+    ICFF(i16)
 }
 
 impl FrameValue {
+    /// Size of the fixed fields at the beginning of the body of the frame `frame_id`
+    fn min_body_size(frame_id : ID3FRAMEID) -> usize {
+        match frame_id {
+            TEXTFRAME(_) | TXXX | ETCO | SYTC => 1,
+            COMM | USLT | USER | APIC => 4,
+            AENC => 5,
+            RBUF => 9,
+            _ => 0
+        }
+    }
+
+    /// Returns `None` if the body is truncated or can't be parsed
     pub (crate) fn new (buffer : &mut Vec<u8>, frame_id : ID3FRAMEID, size : u32) -> Option<Self> {
+        if buffer.len() < (size as usize).max(Self::min_body_size(frame_id)) {
+            return None;
+        }
         match frame_id {
             TEXTFRAME(_) => {
                 let encode = match TextEncoding::from_raw_value(buffer.remove(0)) {
@@ -634,11 +1777,13 @@ impl FrameValue {
                 Some( Self::UIF( UserInfoFrame { text_encoding: encode, description, text}))
             }
             WCOM | WCOP | WOAF | WOAR | WOAS | WORS | WPAY | WPUB => {
-                let url =  String::from_utf8(buffer.drain(0..((size) as usize)).collect()).ok()?;
-                //println!("buffer remain : {}", buffer.len());
-                // println!("url : {}", &url);
-                Some( Self::UF( UrlFrame { url } ))  
+                let url = buffer.drain(0..((size) as usize)).collect::<Vec<u8>>().into_string(&TextEncoding::Iso8859_1)?;
+                Some( Self::UF( UrlFrame { url } ))
             },
+            WXXX => {
+                let data = buffer.drain(0..(size as usize)).collect();
+                Some( Self::UUF(UserUrlFrame::from_bytes(data)?))
+            }
             COMM => {
                 let encode = match TextEncoding::from_raw_value(buffer.remove(0)) {
                     Some(e) => e,
@@ -664,7 +1809,10 @@ impl FrameValue {
                 };
                 // println!("encode : {:?}", encode);
                 //let mime_type = first_string(buffer, &encode, true)?;
-                let mime_type = buffer.first_matched_string(&encode, true)?;
+                let mime_type = buffer.first_matched_string(&TextEncoding::Iso8859_1, true)?;
+                if buffer.is_empty() {
+                    return None;
+                }
                 let picture_type = PictureType::from_raw_value(buffer.remove(0))?;
                 // println!("Mime Type : {}", mime_type);
                 //let description = first_string(buffer, &encode, true)?;
@@ -681,21 +1829,12 @@ impl FrameValue {
                 }))
             },
             IPLS => {
-                let encode = TextEncoding::from_raw_value(buffer.remove(0)).unwrap_or(TextEncoding::Iso8859_1);
-                let people_list = buffer.first_matched_string(&encode, true)?;
-                Some( Self::IPF(
-                        InvolvedPeopleFrame {
-                            text_encoding: encode,
-                            people_list }
-                    )
-                )
+                let data = buffer.drain(0..(size as usize)).collect();
+                Some( Self::IPF(InvolvedPeopleFrame::from_bytes(data)?))
             }
             UFID => {
-                let start_len = buffer.len();
-                //let owner_id = first_string(buffer, &TextEncoding::UnicodeUtf8, true)?;
-                let owner_id = buffer.first_matched_string(&TextEncoding::Iso8859_1, true)?;
-                let drain_size  = size as usize - (start_len - buffer.len()); 
-                let id = buffer.drain(0..drain_size).collect();
+                let mut id = buffer.drain(0..(size as usize)).collect::<Vec<u8>>();
+                let owner_id = id.take_string(&TextEncoding::Iso8859_1)?;
                 Some(Self::UFIF(UniqueFileIdentifierFrame{
                     owner_id,
                     id
@@ -709,32 +1848,67 @@ impl FrameValue {
             }
             ETCO => {
                 let time_stamp_format = TimeStampFormat::from_raw_value(buffer.remove(0))?;
-                let raw = buffer.drain(0..((size-1) as usize)).collect();
+                let mut data = buffer.drain(0..((size-1) as usize)).collect::<Vec<u8>>();
+                let mut events = vec![];
+                while !data.is_empty() {
+                    let event = EventType::from_bytes(&mut data)?;
+                    let timestamp = u32::from_be_bytes(data.get(0..4)?.try_into().ok()?);
+                    data.drain(0..4);
+                    events.push((event, timestamp));
+                }
                 Some(Self::ETF(EventTimingFrame {
                     time_stamp_format,
-                    raw
+                    events
                 } ))
             }
             MLLT => {
-                let raw = buffer.drain(0..(size as usize)).collect();
-                Some( Self::LLTF(LocationLookupTableFrame{
-                    raw
-                }))
+                let data = buffer.drain(0..(size as usize)).collect::<Vec<u8>>();
+                Some( Self::LLTF(LocationLookupTableFrame::from_bytes(&data)?))
             }
             SYTC => {
                 let time_stamp_format = TimeStampFormat::from_raw_value(buffer.remove(0))?;
-                let tempo_data = buffer.drain(0..((size-1) as usize)).collect();
+                let mut data = buffer.drain(0..((size-1) as usize)).collect::<Vec<u8>>();
+                let mut tempos = vec![];
+                while !data.is_empty() {
+                    let mut tempo = data.remove(0) as u16;
+                    if tempo == 0xFF {
+                        tempo += *data.first()? as u16;
+                        data.remove(0);
+                    }
+                    let timestamp = u32::from_be_bytes(data.get(0..4)?.try_into().ok()?);
+                    data.drain(0..4);
+                    tempos.push((tempo, timestamp));
+                }
                 Some(Self::SYCF(SyncTempoCodeFrame {
                     time_stamp_format,
-                    tempo_data
+                    tempos
                 } ))
             }
             SYLT => {
-                let raw = buffer.drain(0..(size as usize)).collect();
+                let mut data = buffer.drain(0..(size as usize)).collect::<Vec<u8>>();
+                if data.len() < 6 {
+                    return None;
+                }
+                let encode = TextEncoding::from_raw_value(data.remove(0)).unwrap_or(TextEncoding::Iso8859_1);
+                let language = String::from_utf8_lossy(&data.drain(0..3).collect::<Vec<u8>>()).to_string();
+                let time_stamp_format = TimeStampFormat::from_raw_value(data.remove(0))?;
+                let content_type = TextContent::from_raw_value(data.remove(0)).unwrap_or(TextContent::Other);
+                let content_description = data.take_string(&encode)?;
+                let mut lyrics = vec![];
+                while !data.is_empty() {
+                    let text = data.take_string(&encode)?;
+                    let timestamp = u32::from_be_bytes(data.get(0..4)?.try_into().ok()?);
+                    data.drain(0..4);
+                    lyrics.push((text, timestamp));
+                }
                 Some(Self::SLF(SyncLyricsFrame{
-                    raw
+                    text_encoding: encode,
+                    language,
+                    time_stamp_format,
+                    content_type,
+                    content_description,
+                    lyrics
                 }))
-
             }
             USLT => {
                 let encode = TextEncoding::from_raw_value(buffer.remove(0)).unwrap_or(TextEncoding::Iso8859_1);
@@ -752,53 +1926,47 @@ impl FrameValue {
                 ))
             }
             RVAD => {
-                let raw = buffer.drain(0..(size as usize)).collect();
-                Some( Self::RVAF(RelativeVolumeAdjustementFrame{
-                    raw
-                }))
+                let data = buffer.drain(0..(size as usize)).collect();
+                Some( Self::RVAF(RelativeVolumeAdjustementFrame::from_rvad_bytes(data)?))
+            }
+            RVA2 => {
+                let data = buffer.drain(0..(size as usize)).collect();
+                Some( Self::RVAF(RelativeVolumeAdjustementFrame::from_rva2_bytes(data)?))
             }
             EQUA => {
-                let raw = buffer.drain(0..(size as usize)).collect();
-                Some( Self::EF(EqualiisationFrame{
-                    raw
-                }))
+                let data = buffer.drain(0..(size as usize)).collect::<Vec<u8>>();
+                Some( Self::EF(EqualiisationFrame::from_bytes(&data)?))
+            }
+            EQU2 => {
+                let data = buffer.drain(0..(size as usize)).collect();
+                Some( Self::E2F(Equalisation2Frame::from_bytes(data)?))
             }
             RVRB => {
-                let raw = buffer.drain(0..(size as usize)).collect();
-                Some( Self::RF(ReverbFrame{
-                    raw
-                }))
+                let data = buffer.drain(0..(size as usize)).collect::<Vec<u8>>();
+                Some( Self::RF(ReverbFrame::from_bytes(&data)?))
             }
             GEOB => {
-                let start_size = buffer.len();
-                let encode = TextEncoding::from_raw_value(buffer.remove(0)).unwrap_or(TextEncoding::Iso8859_1);
-                let mime_type = buffer.first_matched_string(&encode, true)?;
-                let filename =  buffer.first_matched_string(&encode, true)?;
-                let content = if encode.is_one_byte() {
-                    buffer.drain(0..1).collect()
-                }else {
-                    buffer.drain(0..2).collect()
-                };
-                let end_size = start_size - buffer.len();
-                let encapsulated_object = buffer.drain(0..( (size - end_size as u32) as usize)).collect();
-                Some(Self::GEOF(GeneralEncapsulatedObjectFrame {
-                    text_encoding: encode,
-                    mime_type,
-                    filename,
-                    _content: content,
-                    encapsulated_object
-                }))
+                let data = buffer.drain(0..(size as usize)).collect();
+                Some(Self::GEOF(GeneralEncapsulatedObjectFrame::from_bytes(data)?))
             }
             PCNT => {
-                let raw = buffer.drain(0..(size as usize)).collect();
+                let data = buffer.drain(0..(size as usize)).collect::<Vec<u8>>();
                 Some( Self::PCF(PlayCounterFrame{
-                    raw
+                    counter: counter_from_bytes(&data)
                 }))
             }
             POPM => {
-                let raw = buffer.drain(0..(size as usize)).collect();
+                let mut data = buffer.drain(0..(size as usize)).collect::<Vec<u8>>();
+                let email = data.take_string(&TextEncoding::Iso8859_1)?;
+                if data.is_empty() {
+                    return None;
+                }
+                let rating = data.remove(0);
+                let counter = if data.is_empty() { None } else { Some(counter_from_bytes(&data)) };
                 Some( Self::PF(PopularimeterFrame{
-                    raw
+                    email,
+                    rating,
+                    counter
                 }))
             }
             RBUF => {
@@ -815,6 +1983,9 @@ impl FrameValue {
                 let start_size = buffer.len();
                 //let owner_id = first_string(buffer, &TextEncoding::UnicodeUtf8, true)?;
                 let owner_id = buffer.first_matched_string(&TextEncoding::Iso8859_1, true)?;
+                if buffer.len() < 4 {
+                    return None;
+                }
                 let preview_start  = buffer.drain(0..2).collect::<Vec<u8>>().u16_from_be()?;
                 let preview_lenght  = buffer.drain(0..2).collect::<Vec<u8>>().u16_from_be()?;
                 let end_size = start_size - buffer.len();
@@ -827,16 +1998,12 @@ impl FrameValue {
                 }))
             }
             LINK => {
-                let raw = buffer.drain(0..(size as usize)).collect();
-                Some( Self::LIF(LinkedInfoFrame{
-                    raw
-                }))
+                let data = buffer.drain(0..(size as usize)).collect();
+                Some( Self::LIF(LinkedInfoFrame::from_bytes(data)?))
             }
             POSS => {
-                let raw = buffer.drain(0..(size as usize)).collect();
-                Some( Self::PSF(PositionSyncFrame{
-                    raw
-                }))
+                let data = buffer.drain(0..(size as usize)).collect::<Vec<u8>>();
+                Some( Self::PSF(PositionSyncFrame::from_bytes(&data)?))
             }
             USER => {
                 let encode = TextEncoding::from_raw_value(buffer.remove(0)).unwrap_or(TextEncoding::Iso8859_1);
@@ -876,15 +2043,9 @@ impl FrameValue {
                 }))
             }
             PRIV => {
-                let start_len = buffer.len();
-                //let owner_id = first_string(buffer, &TextEncoding::UnicodeUtf8, true)?;
-                let owner_id = buffer.first_matched_string(&TextEncoding::Iso8859_1, true)?;
-                let end_len = start_len - buffer.len();
-                let private_data = buffer.drain(0..(size as usize - end_len)).collect();
-                Some(Self::PrivF(PrivateFrame {
-                    owner_id,
-                    private_data
-                }))
+                let mut data = buffer.drain(0..(size as usize)).collect::<Vec<u8>>();
+                let owner_id = data.take_string(&TextEncoding::Iso8859_1)?;
+                Some(Self::PrivF(PrivateFrame::new(&owner_id, data)))
             }
             _ =>  Some( Self::Undefined(buffer.drain(0..(size as usize)).collect()) )
         }
@@ -898,6 +2059,7 @@ impl RawSize for FrameValue {
             FrameValue::TF(fv) => fv.raw_size(),
             FrameValue::UIF(fv) => fv.raw_size(),
             FrameValue::UF(fv) => fv.raw_size(),
+            FrameValue::UUF(fv) => fv.raw_size(),
             FrameValue::IPF(fv) => fv.raw_size(),
             FrameValue::MCIF(fv) => fv.raw_size(),
             FrameValue::ETF(fv) => fv.raw_size(),
@@ -908,6 +2070,7 @@ impl RawSize for FrameValue {
             FrameValue::CF(fv) => fv.raw_size(),
             FrameValue::RVAF(fv) => fv.raw_size(),
             FrameValue::EF(fv) => fv.raw_size(),
+            FrameValue::E2F(fv) => fv.raw_size(),
             FrameValue::RF(fv) => fv.raw_size(),
             FrameValue::APF(fv) => fv.raw_size(),
             FrameValue::GEOF(fv) => fv.raw_size(),
@@ -925,7 +2088,6 @@ impl RawSize for FrameValue {
             FrameValue::PrivF(fv) => fv.raw_size(),
             FrameValue::Undefined(raw) => raw.len(),
             FrameValue::ICFF(_) => 3,
-            Self::NoValue => 0,
         }
     }
 
@@ -935,6 +2097,7 @@ impl RawSize for FrameValue {
             FrameValue::TF(fv) => fv.raw_bytes(),
             FrameValue::UIF(fv) => fv.raw_bytes(),
             FrameValue::UF(fv) => fv.raw_bytes(),
+            FrameValue::UUF(fv) => fv.raw_bytes(),
             FrameValue::IPF(fv) => fv.raw_bytes(),
             FrameValue::MCIF(fv) => fv.raw_bytes(),
             FrameValue::ETF(fv) => fv.raw_bytes(),
@@ -945,6 +2108,7 @@ impl RawSize for FrameValue {
             FrameValue::CF(fv) => fv.raw_bytes(),
             FrameValue::RVAF(fv) => fv.raw_bytes(),
             FrameValue::EF(fv) => fv.raw_bytes(),
+            FrameValue::E2F(fv) => fv.raw_bytes(),
             FrameValue::RF(fv) => fv.raw_bytes(),
             FrameValue::APF(fv) => fv.raw_bytes(),
             FrameValue::GEOF(fv) => fv.raw_bytes(),
@@ -962,13 +2126,28 @@ impl RawSize for FrameValue {
             FrameValue::PrivF(fv) => fv.raw_bytes(),
             FrameValue::Undefined(raw) => raw.clone(),
             FrameValue::ICFF(c) => c.to_be_bytes().to_vec(),
-            Self::NoValue => vec![],
         }
     }
 }
 
 
 impl FrameValue {
+    /// Replace the text encodings which aren't allowed by the ID3v2.`version` specification
+    pub (crate) fn adapt_text_encoding(&mut self, version : u8) {
+        match self {
+            Self::TF(fv) => fv.text_encoding = fv.text_encoding.for_version(version),
+            Self::UIF(fv) => fv.text_encoding = fv.text_encoding.for_version(version),
+            Self::IPF(fv) => fv.text_encoding = fv.text_encoding.for_version(version),
+            Self::ULF(fv) => fv.text_encoding = fv.text_encoding.for_version(version),
+            Self::CF(fv) => fv.text_encoding = fv.text_encoding.for_version(version),
+            Self::APF(fv) => fv.text_encode = fv.text_encode.for_version(version),
+            Self::GEOF(fv) => fv.text_encoding = fv.text_encoding.for_version(version),
+            Self::TUF(fv) => fv.text_encoding = fv.text_encoding.for_version(version),
+            Self::UUF(fv) => fv.text_encoding = fv.text_encoding.for_version(version),
+            Self::SLF(fv) => fv.text_encoding = fv.text_encoding.for_version(version),
+            _ => ()
+        }
+    }
     pub (crate) fn as_attached_picture_frame(&self) -> Option<&AttachedPictureFrame>{
         match self {
             Self::APF(picture_frame) => Some(picture_frame),
@@ -1007,6 +2186,145 @@ impl FrameValue {
             _ => None
         }
     }
+    pub (crate) fn as_sync_lyrics_frame(&self) -> Option<&SyncLyricsFrame>{
+        match self {
+            Self::SLF(f) => Some(f),
+            _ => None
+        }
+    }
+    #[allow(dead_code)]
+    pub (crate) fn as_sync_lyrics_frame_mut(&mut self) -> Option<&mut SyncLyricsFrame>{
+        match self {
+            Self::SLF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_event_timing_frame(&self) -> Option<&EventTimingFrame> {
+        match self {
+            Self::ETF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_sync_tempo_code_frame(&self) -> Option<&SyncTempoCodeFrame> {
+        match self {
+            Self::SYCF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_location_lookup_table_frame(&self) -> Option<&LocationLookupTableFrame> {
+        match self {
+            Self::LLTF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_relative_volume_frame(&self) -> Option<&RelativeVolumeAdjustementFrame> {
+        match self {
+            Self::RVAF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_user_info_frame(&self) -> Option<&UserInfoFrame> {
+        match self {
+            Self::UIF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_linked_info_frame(&self) -> Option<&LinkedInfoFrame> {
+        match self {
+            Self::LIF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_position_sync_frame(&self) -> Option<&PositionSyncFrame> {
+        match self {
+            Self::PSF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_music_cd_id_frame(&self) -> Option<&MusicCdIdframe> {
+        match self {
+            Self::MCIF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_involved_people_frame(&self) -> Option<&InvolvedPeopleFrame> {
+        match self {
+            Self::IPF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_url_frame(&self) -> Option<&UrlFrame> {
+        match self {
+            Self::UF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_user_url_frame(&self) -> Option<&UserUrlFrame> {
+        match self {
+            Self::UUF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_unique_file_identifier_frame(&self) -> Option<&UniqueFileIdentifierFrame> {
+        match self {
+            Self::UFIF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_private_frame(&self) -> Option<&PrivateFrame> {
+        match self {
+            Self::PrivF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_encapsulated_object_frame(&self) -> Option<&GeneralEncapsulatedObjectFrame> {
+        match self {
+            Self::GEOF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_equalisation_frame(&self) -> Option<&EqualiisationFrame> {
+        match self {
+            Self::EF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_equalisation2_frame(&self) -> Option<&Equalisation2Frame> {
+        match self {
+            Self::E2F(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_reverb_frame(&self) -> Option<&ReverbFrame> {
+        match self {
+            Self::RF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_play_counter_frame(&self) -> Option<&PlayCounterFrame> {
+        match self {
+            Self::PCF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_play_counter_frame_mut(&mut self) -> Option<&mut PlayCounterFrame> {
+        match self {
+            Self::PCF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_popularimeter_frame(&self) -> Option<&PopularimeterFrame> {
+        match self {
+            Self::PF(f) => Some(f),
+            _ => None
+        }
+    }
+    pub(crate) fn as_popularimeter_frame_mut(&mut self) -> Option<&mut PopularimeterFrame> {
+        match self {
+            Self::PF(f) => Some(f),
+            _ => None
+        }
+    }
     pub(crate) fn as_comment_frame(&self) -> Option<&CommentFrame> {
         match self {
             Self::CF(cf) => Some(cf),
@@ -1,4 +1,6 @@
-use std::{fmt::Display, str::FromStr};
+use std::{convert::TryInto, fmt::Display, str::FromStr};
+
+use super::id3_version::ID3Version;
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub(crate) enum ID3FRAMEID {
@@ -70,6 +72,18 @@ pub(crate) enum ID3FRAMEID {
     WPUB,
     /// WXXX User defined URL link frame]
     WXXX,
+    /// ASPI Audio seek point index (ID3v2.4)
+    ASPI,
+    /// EQU2 Equalisation (2) (ID3v2.4)
+    EQU2,
+    /// RVA2 Relative volume adjustment (2) (ID3v2.4)
+    RVA2,
+    /// SEEK Seek frame (ID3v2.4)
+    SEEK,
+    /// SIGN Signature frame (ID3v2.4)
+    SIGN,
+    /// Frame not defined by the specification, kept as it is
+    UNKNOWN([u8; 4]),
 }
 
 impl FromStr for ID3FRAMEID {
@@ -117,6 +131,11 @@ impl FromStr for ID3FRAMEID {
             "WPAY" => Ok(ID3FRAMEID::WPAY),
             "WPUB" => Ok(ID3FRAMEID::WPUB),
             "WXXX" => Ok(ID3FRAMEID::WXXX),
+            "ASPI" => Ok(ID3FRAMEID::ASPI),
+            "EQU2" => Ok(ID3FRAMEID::EQU2),
+            "RVA2" => Ok(ID3FRAMEID::RVA2),
+            "SEEK" => Ok(ID3FRAMEID::SEEK),
+            "SIGN" => Ok(ID3FRAMEID::SIGN),
             _ => Err(()),
         }
 
@@ -124,6 +143,100 @@ impl FromStr for ID3FRAMEID {
     }
 }
 
+impl ID3FRAMEID {
+    /// Parse a frame identifier, keeping the identifiers not defined by the specification
+    ///
+    /// Fails if the identifier isn't made of capital letters and digits
+    pub(crate) fn from_raw_id(id: &[u8]) -> Result<Self, ()> {
+        let id : [u8; 4] = id.try_into().map_err(|_| ())?;
+        if !id.iter().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()) {
+            return Err(());
+        }
+        let s = std::str::from_utf8(&id).map_err(|_| ())?;
+        Ok(Self::from_str(s).unwrap_or(Self::UNKNOWN(id)))
+    }
+
+    /// Whether the frame isn't defined by the specification
+    pub(crate) fn is_unknown(&self) -> bool {
+        matches!(self, Self::UNKNOWN(_))
+    }
+
+    /// Map an ID3v2.2 three-letter frame identifier to its ID3v2.3 counterpart
+    pub(crate) fn from_v22_str(s: &str) -> Result<Self, ()> {
+        let frame_id = match s {
+            "BUF" => "RBUF",
+            "CNT" => "PCNT",
+            "COM" => "COMM",
+            "CRA" => "AENC",
+            "EQU" => "EQUA",
+            "ETC" => "ETCO",
+            "GEO" => "GEOB",
+            "IPL" => "IPLS",
+            "LNK" => "LINK",
+            "MCI" => "MCDI",
+            "MLL" => "MLLT",
+            "PIC" => "APIC",
+            "POP" => "POPM",
+            "REV" => "RVRB",
+            "RVA" => "RVAD",
+            "SLT" => "SYLT",
+            "STC" => "SYTC",
+            "TAL" => "TALB",
+            "TBP" => "TBPM",
+            "TCM" => "TCOM",
+            "TCO" => "TCON",
+            "TCP" => "TCMP",
+            "TCR" => "TCOP",
+            "TDA" => "TDAT",
+            "TDY" => "TDLY",
+            "TEN" => "TENC",
+            "TFT" => "TFLT",
+            "TIM" => "TIME",
+            "TKE" => "TKEY",
+            "TLA" => "TLAN",
+            "TLE" => "TLEN",
+            "TMT" => "TMED",
+            "TOA" => "TOPE",
+            "TOF" => "TOFN",
+            "TOL" => "TOLY",
+            "TOR" => "TORY",
+            "TOT" => "TOAL",
+            "TP1" => "TPE1",
+            "TP2" => "TPE2",
+            "TP3" => "TPE3",
+            "TP4" => "TPE4",
+            "TPA" => "TPOS",
+            "TPB" => "TPUB",
+            "TRC" => "TSRC",
+            "TRD" => "TRDA",
+            "TRK" => "TRCK",
+            "TS2" => "TSO2",
+            "TSA" => "TSOA",
+            "TSI" => "TSIZ",
+            "TSP" => "TSOP",
+            "TSS" => "TSSE",
+            "TST" => "TSOT",
+            "TT1" => "TIT1",
+            "TT2" => "TIT2",
+            "TT3" => "TIT3",
+            "TXT" => "TEXT",
+            "TXX" => "TXXX",
+            "TYE" => "TYER",
+            "UFI" => "UFID",
+            "ULT" => "USLT",
+            "WAF" => "WOAF",
+            "WAR" => "WOAR",
+            "WAS" => "WOAS",
+            "WCM" => "WCOM",
+            "WCP" => "WCOP",
+            "WPB" => "WPUB",
+            "WXX" => "WXXX",
+            _ => return Err(()),
+        };
+        Self::from_str(frame_id)
+    }
+}
+
 impl Display for ID3FRAMEID {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
@@ -164,7 +277,13 @@ impl Display for ID3FRAMEID {
             ID3FRAMEID::WPUB => "WPUB".to_string(),
             ID3FRAMEID::WXXX => "WXXX".to_string(),
             ID3FRAMEID::TCMP => "TCMP".to_string(),
+            ID3FRAMEID::ASPI => "ASPI".to_string(),
+            ID3FRAMEID::EQU2 => "EQU2".to_string(),
+            ID3FRAMEID::RVA2 => "RVA2".to_string(),
+            ID3FRAMEID::SEEK => "SEEK".to_string(),
+            ID3FRAMEID::SIGN => "SIGN".to_string(),
             ID3FRAMEID::TEXTFRAME(frame) => frame.to_string(),
+            ID3FRAMEID::UNKNOWN(id) => String::from_utf8_lossy(id).to_string(),
         };
         write!(f, "{}", s)
     }
@@ -174,7 +293,17 @@ impl ID3FRAMEID {
     #[allow(dead_code)]
     pub(crate) fn is_text_frame(&self) -> bool {
         let frame_name = self.to_string();
-        frame_name.starts_with("T") && frame_name != "TXXX" && frame_name != "TCMP"
+        !self.is_unknown() && frame_name.starts_with("T") && frame_name != "TXXX" && frame_name != "TCMP"
+    }
+
+    /// Whether the frame is defined by the `version` of the ID3v2 specification
+    pub(crate) fn is_supported_by(&self, version: ID3Version) -> bool {
+        match self {
+            Self::TEXTFRAME(tf) => tf.is_supported_by(version),
+            Self::ASPI | Self::EQU2 | Self::RVA2 | Self::SEEK | Self::SIGN => version == ID3Version::V2_4,
+            Self::EQUA | Self::IPLS | Self::RVAD => version == ID3Version::V2_3,
+            _ => true
+        }
     }
 }
 
@@ -260,6 +389,30 @@ pub enum ID3TEXTFRAMEID {
     TSSE,
     /// TYER Year
     TYER,
+    /// TDEN Encoding time (ID3v2.4)
+    TDEN,
+    /// TDOR Original release time (ID3v2.4)
+    TDOR,
+    /// TDRC Recording time (ID3v2.4)
+    TDRC,
+    /// TDRL Release time (ID3v2.4)
+    TDRL,
+    /// TDTG Tagging time (ID3v2.4)
+    TDTG,
+    /// TIPL Involved people list (ID3v2.4)
+    TIPL,
+    /// TMCL Musician credits list (ID3v2.4)
+    TMCL,
+    /// TMOO Mood (ID3v2.4)
+    TMOO,
+    /// TPRO Produced notice (ID3v2.4)
+    TPRO,
+    /// TSOA Album sort order (ID3v2.4)
+    TSOA,
+    /// TSOP Performer sort order (ID3v2.4)
+    TSOP,
+    /// TSST Set subtitle (ID3v2.4)
+    TSST,
 }
 
 impl FromStr for ID3TEXTFRAMEID {
@@ -307,6 +460,18 @@ impl FromStr for ID3TEXTFRAMEID {
             "TSRC" => Ok(Self::TSRC),
             "TSSE" => Ok(Self::TSSE),
             "TYER" => Ok(Self::TYER),
+            "TDEN" => Ok(Self::TDEN),
+            "TDOR" => Ok(Self::TDOR),
+            "TDRC" => Ok(Self::TDRC),
+            "TDRL" => Ok(Self::TDRL),
+            "TDTG" => Ok(Self::TDTG),
+            "TIPL" => Ok(Self::TIPL),
+            "TMCL" => Ok(Self::TMCL),
+            "TMOO" => Ok(Self::TMOO),
+            "TPRO" => Ok(Self::TPRO),
+            "TSOA" => Ok(Self::TSOA),
+            "TSOP" => Ok(Self::TSOP),
+            "TSST" => Ok(Self::TSST),
             _ => Err(()),
         }
     }
@@ -355,7 +520,86 @@ impl Display for ID3TEXTFRAMEID {
          Self::TSRC => "TSRC",
          Self::TSSE => "TSSE",
          Self::TYER => "TYER",
+         Self::TDEN => "TDEN",
+         Self::TDOR => "TDOR",
+         Self::TDRC => "TDRC",
+         Self::TDRL => "TDRL",
+         Self::TDTG => "TDTG",
+         Self::TIPL => "TIPL",
+         Self::TMCL => "TMCL",
+         Self::TMOO => "TMOO",
+         Self::TPRO => "TPRO",
+         Self::TSOA => "TSOA",
+         Self::TSOP => "TSOP",
+         Self::TSST => "TSST",
         };
         write!(f, "{}", s)
     }
 }
+
+impl ID3TEXTFRAMEID {
+    /// Whether the text frame is defined by the `version` of the ID3v2 specification
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::{ID3TEXTFRAMEID, ID3Version};
+    /// assert!(ID3TEXTFRAMEID::TYER.is_supported_by(ID3Version::V2_3));
+    /// assert!(!ID3TEXTFRAMEID::TYER.is_supported_by(ID3Version::V2_4));
+    /// assert!(ID3TEXTFRAMEID::TDRC.is_supported_by(ID3Version::V2_4));
+    /// ```
+    pub fn is_supported_by(&self, version: ID3Version) -> bool {
+        match self {
+            Self::TDAT | Self::TIME | Self::TORY | Self::TRDA | Self::TSIZ | Self::TYER => {
+                version == ID3Version::V2_3
+            }
+            Self::TDEN | Self::TDOR | Self::TDRC | Self::TDRL | Self::TDTG | Self::TIPL
+            | Self::TMCL | Self::TMOO | Self::TPRO | Self::TSOA | Self::TSOP | Self::TSOT | Self::TSST => {
+                version == ID3Version::V2_4
+            }
+            _ => true
+        }
+    }
+}
+
+/// URL link frame Code
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ID3URLFRAMEID {
+    /// WCOM Commercial information
+    WCOM,
+    /// WCOP Copyright/Legal information
+    WCOP,
+    /// WOAF Official audio file webpage
+    WOAF,
+    /// WOAR Official artist/performer webpage
+    WOAR,
+    /// WOAS Official audio source webpage
+    WOAS,
+    /// WORS Official internet radio station homepage
+    WORS,
+    /// WPAY Payment
+    WPAY,
+    /// WPUB Publishers official webpage
+    WPUB
+}
+
+impl ID3URLFRAMEID {
+    /// Whether the tag can contain several frames of this kind, with different URLs
+    pub fn allows_multiple(&self) -> bool {
+        matches!(self, Self::WCOM | Self::WOAR)
+    }
+}
+
+impl From<ID3URLFRAMEID> for ID3FRAMEID {
+    fn from(url_frame_id: ID3URLFRAMEID) -> Self {
+        match url_frame_id {
+            ID3URLFRAMEID::WCOM => Self::WCOM,
+            ID3URLFRAMEID::WCOP => Self::WCOP,
+            ID3URLFRAMEID::WOAF => Self::WOAF,
+            ID3URLFRAMEID::WOAR => Self::WOAR,
+            ID3URLFRAMEID::WOAS => Self::WOAS,
+            ID3URLFRAMEID::WORS => Self::WORS,
+            ID3URLFRAMEID::WPAY => Self::WPAY,
+            ID3URLFRAMEID::WPUB => Self::WPUB
+        }
+    }
+}
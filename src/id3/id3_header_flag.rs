@@ -3,19 +3,114 @@
 pub(crate) enum ID3HeaderFLAG {
     Unsynchronisation = 0b10_000_000,
     ExtendedHeader = 0b01_000_000,
-    ExperimentalIndicator = 0b00_100_000
+    ExperimentalIndicator = 0b00_100_000,
+    /// ID3v2.4 only
+    FooterPresent = 0b00_010_000
 }
 #[repr(u8)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub(crate) enum ID3FRAMEHEADERFLAGSB1 {
     TagAlterPreservation = 0b10_000_000,
     FileAlterPreservation = 0b01_000_000,
     ReadOnly = 0b00_100_000
 }
 #[repr(u8)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Eq)]
 pub(crate) enum ID3FRAMEHEADERFLAGSB2 {
     Compression = 0b10_000_000,
     Encryption = 0b01_000_000,
-    GroupingIdentity = 0b00_100_000
+    GroupingIdentity = 0b00_100_000,
+    /// ID3v2.4 only
+    Unsynchronisation = 0b00_000_010,
+    /// ID3v2.4 only
+    DataLengthIndicator = 0b00_000_001
+}
+
+/// Status flags of an ID3 frame
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct FrameStatusFlags {
+    /// The frame is discarded when the tag is altered (tag alter preservation)
+    pub discard_on_tag_alteration : bool,
+    /// The frame is discarded when the audio data is altered (file alter preservation)
+    pub discard_on_file_alteration : bool,
+    /// The frame can't be edited unless forced, see [crate::ID3TAG::set_force_read_only_edits]
+    pub read_only : bool
+}
+
+impl FrameStatusFlags {
+    pub(crate) fn from_flags(flags : &[ID3FRAMEHEADERFLAGSB1]) -> Self {
+        Self {
+            discard_on_tag_alteration: flags.contains(&ID3FRAMEHEADERFLAGSB1::TagAlterPreservation),
+            discard_on_file_alteration: flags.contains(&ID3FRAMEHEADERFLAGSB1::FileAlterPreservation),
+            read_only: flags.contains(&ID3FRAMEHEADERFLAGSB1::ReadOnly)
+        }
+    }
+
+    pub(crate) fn to_flags(self) -> Vec<ID3FRAMEHEADERFLAGSB1> {
+        let mut flags = vec![];
+        if self.discard_on_tag_alteration {
+            flags.push(ID3FRAMEHEADERFLAGSB1::TagAlterPreservation)
+        }
+        if self.discard_on_file_alteration {
+            flags.push(ID3FRAMEHEADERFLAGSB1::FileAlterPreservation)
+        }
+        if self.read_only {
+            flags.push(ID3FRAMEHEADERFLAGSB1::ReadOnly)
+        }
+        flags
+    }
+}
+
+impl ID3FRAMEHEADERFLAGSB1 {
+    const ALL : [Self; 3] = [Self::TagAlterPreservation, Self::FileAlterPreservation, Self::ReadOnly];
+
+    /// Bit of the flag in the first flag byte for the tag `version`
+    pub(crate) fn raw_value(&self, version : u8) -> u8 {
+        if version == 4 {
+            (*self as u8) >> 1
+        } else {
+            *self as u8
+        }
+    }
+
+    pub(crate) fn from_flag_byte(byte : u8, version : u8) -> Vec<Self> {
+        Self::ALL.iter()
+        .filter(|flag| byte & flag.raw_value(version) == flag.raw_value(version))
+        .copied()
+        .collect()
+    }
+}
+
+impl ID3FRAMEHEADERFLAGSB2 {
+    const ALL : [Self; 5] = [
+        Self::Compression, Self::Encryption, Self::GroupingIdentity,
+        Self::Unsynchronisation, Self::DataLengthIndicator
+    ];
+
+    /// Bit of the flag in the second flag byte for the tag `version`
+    ///
+    /// Returns 0 if the flag doesn't exist in this version
+    pub(crate) fn raw_value(&self, version : u8) -> u8 {
+        if version == 4 {
+            match self {
+                Self::GroupingIdentity => 0b01_000_000,
+                Self::Compression => 0b00_001_000,
+                Self::Encryption => 0b00_000_100,
+                Self::Unsynchronisation => 0b00_000_010,
+                Self::DataLengthIndicator => 0b00_000_001
+            }
+        } else {
+            match self {
+                Self::Unsynchronisation | Self::DataLengthIndicator => 0,
+                _ => *self as u8
+            }
+        }
+    }
+
+    pub(crate) fn from_flag_byte(byte : u8, version : u8) -> Vec<Self> {
+        Self::ALL.iter()
+        .filter(|flag| flag.raw_value(version) != 0 && byte & flag.raw_value(version) == flag.raw_value(version))
+        .copied()
+        .collect()
+    }
 }
@@ -0,0 +1,81 @@
+//! Conversion between the synchronised lyrics and the LRC format
+//!
+//! Only the line synchronised LRC format is supported : the word timestamps of the
+//! enhanced format (`<mm:ss.xx>`) are kept as text
+
+/// Parse a LRC timestamp (`mm:ss`, `mm:ss.xx`, `mm:ss.xxx` or `mm:ss:xx`) in milliseconds
+fn parse_timestamp(timestamp : &str) -> Option<u32> {
+    let (minutes, rest) = timestamp.split_once(':')?;
+    let (seconds, fraction) = match rest.find(['.', ':']) {
+        Some(index) => (&rest[..index], &rest[(index + 1)..]),
+        None => (rest, "")
+    };
+    let minutes = minutes.trim().parse::<u32>().ok()?;
+    let seconds = seconds.parse::<u32>().ok()?;
+    if seconds >= 60 || !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    // Only the first 3 digits are meaningful
+    let fraction = fraction.chars().chain(std::iter::repeat('0')).take(3).collect::<String>();
+    let milliseconds = fraction.parse::<u32>().ok()?;
+    Some(minutes * 60_000 + seconds * 1000 + milliseconds)
+}
+
+fn format_timestamp(milliseconds : u32) -> String {
+    let hundredths = milliseconds / 10;
+    format!("[{:02}:{:02}.{:02}]", hundredths / 6000, (hundredths / 100) % 60, hundredths % 100)
+}
+
+/// Parse the content of a LRC file into a list of (text, timestamp in milliseconds) sorted by time
+///
+/// The metadata tags (`[ar:...]`, `[ti:...]`, ...) are ignored, except `[offset:...]` which is applied to the timestamps.
+/// A line with several timestamps gives an entry for each of them
+pub(crate) fn parse_lrc(lrc : &str) -> Vec<(String, u32)> {
+    let mut offset = 0i64;
+    let mut lines = vec![];
+    for line in lrc.lines() {
+        let mut rest = line.trim();
+        let mut timestamps = vec![];
+        while let Some(tag) = rest.strip_prefix('[') {
+            let end = match tag.find(']') {
+                Some(end) => end,
+                None => break
+            };
+            let content = &tag[..end];
+            if let Some(timestamp) = parse_timestamp(content) {
+                timestamps.push(timestamp)
+            } else if let Some(value) = content.strip_prefix("offset:") {
+                offset = value.trim().parse().unwrap_or(0)
+            }
+            rest = &tag[(end + 1)..];
+        }
+        for timestamp in timestamps {
+            lines.push((rest.trim().to_string(), timestamp))
+        }
+    }
+    // A positive offset shows the lyrics sooner
+    let mut lyrics = lines.into_iter()
+    .map(|(text, timestamp)| (text, (timestamp as i64 - offset).clamp(0, u32::MAX as i64) as u32))
+    .collect::<Vec<(String, u32)>>();
+    lyrics.sort_by_key(|(_, timestamp)| *timestamp);
+    lyrics
+}
+
+/// Format a list of (text, timestamp in milliseconds) as a LRC file
+///
+/// If some texts begin with a newline, the entries which don't are appended to the previous line
+/// (syllable synchronised lyrics). Otherwise each entry is a line.
+pub(crate) fn format_lrc(lyrics : &[(String, u32)]) -> String {
+    let is_new_line = |text : &str| text.starts_with('\n') || text.starts_with('\r');
+    let by_syllable = lyrics.iter().any(|(text, _)| is_new_line(text));
+    let mut lines : Vec<(u32, String)> = vec![];
+    for (text, timestamp) in lyrics {
+        match lines.last_mut() {
+            Some((_, line)) if by_syllable && !is_new_line(text) => line.push_str(text),
+            _ => lines.push((*timestamp, text.trim_start_matches(['\r', '\n']).to_string()))
+        }
+    }
+    lines.iter()
+    .map(|(timestamp, line)| format!("{}{}\n", format_timestamp(*timestamp), line))
+    .collect()
+}
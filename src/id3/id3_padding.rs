@@ -0,0 +1,39 @@
+/// Alignment of [PaddingPolicy::Aligned4KiB]
+const ALIGNMENT : usize = 4096;
+
+/// Padding added after the frames when the tag doesn't fit in the space it had in the file
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PaddingPolicy {
+    /// A fixed number of bytes
+    Fixed(usize),
+    /// A percentage of the size of the tag without padding
+    Percentage(u8),
+    /// As many bytes as needed for the tag to end on a 4 KiB boundary
+    Aligned4KiB
+}
+
+impl Default for PaddingPolicy {
+    fn default() -> Self {
+        Self::Fixed(10_000)
+    }
+}
+
+impl PaddingPolicy {
+    /// Padding size for a tag of `tag_size` bytes without padding
+    pub(crate) fn padding(&self, tag_size : usize) -> usize {
+        match self {
+            Self::Fixed(size) => *size,
+            Self::Percentage(percentage) => tag_size * (*percentage as usize) / 100,
+            Self::Aligned4KiB => (ALIGNMENT - tag_size % ALIGNMENT) % ALIGNMENT
+        }
+    }
+}
+
+/// How a tag has been written back to its file
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WriteStrategy {
+    /// The tag fitted in the space of the previous tag, only the tag region has been rewritten
+    InPlace,
+    /// The whole file has been rewritten with a padding following the [PaddingPolicy]
+    FullRewrite
+}
@@ -0,0 +1,97 @@
+//! Decoding of the private frames (PRIV) written by well-known owners
+
+use std::convert::TryInto;
+
+/// Content of a private frame whose owner is known
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PrivateContent {
+    /// Primary media class of Windows Media Player, as a GUID (`{D1607DBC-E323-4BE2-86A1-48A42A28441E}` for music)
+    MediaClassPrimaryID(String),
+    /// Secondary media class of Windows Media Player, as a GUID
+    MediaClassSecondaryID(String),
+    /// Identifier of the collection (album) in Windows Media Player, as a GUID
+    WMCollectionID(String),
+    /// Identifier of the collection group in Windows Media Player, as a GUID
+    WMCollectionGroupID(String),
+    /// Identifier of the content in Windows Media Player, as a GUID
+    WMContentID(String),
+    /// Average volume level computed by Windows Media Player
+    AverageLevel(u32),
+    /// Peak volume level computed by Windows Media Player
+    PeakValue(u32),
+    /// XMP packet (XML)
+    Xmp(String)
+}
+
+impl PrivateContent {
+    /// Decode the private data of the owner `owner_id`
+    ///
+    /// `None` if the owner is unknown or the data is malformed
+    pub(crate) fn from_bytes(owner_id : &str, data : &[u8]) -> Option<Self> {
+        let content = match owner_id {
+            "WM/MediaClassPrimaryID" => Self::MediaClassPrimaryID(guid_to_string(data)?),
+            "WM/MediaClassSecondaryID" => Self::MediaClassSecondaryID(guid_to_string(data)?),
+            "WM/WMCollectionID" => Self::WMCollectionID(guid_to_string(data)?),
+            "WM/WMCollectionGroupID" => Self::WMCollectionGroupID(guid_to_string(data)?),
+            "WM/WMContentID" => Self::WMContentID(guid_to_string(data)?),
+            "AverageLevel" => Self::AverageLevel(u32::from_le_bytes(data.try_into().ok()?)),
+            "PeakValue" => Self::PeakValue(u32::from_le_bytes(data.try_into().ok()?)),
+            "XMP" => Self::Xmp(String::from_utf8(data.to_vec()).ok()?.trim_end_matches('\u{0}').to_string()),
+            _ => return None
+        };
+        Some(content)
+    }
+
+    /// Returns the owner identifier of the private frame
+    pub fn owner_id(&self) -> &'static str {
+        match self {
+            Self::MediaClassPrimaryID(_) => "WM/MediaClassPrimaryID",
+            Self::MediaClassSecondaryID(_) => "WM/MediaClassSecondaryID",
+            Self::WMCollectionID(_) => "WM/WMCollectionID",
+            Self::WMCollectionGroupID(_) => "WM/WMCollectionGroupID",
+            Self::WMContentID(_) => "WM/WMContentID",
+            Self::AverageLevel(_) => "AverageLevel",
+            Self::PeakValue(_) => "PeakValue",
+            Self::Xmp(_) => "XMP"
+        }
+    }
+
+    /// Encode the private data
+    ///
+    /// `None` if a GUID is malformed
+    pub(crate) fn to_bytes(&self) -> Option<Vec<u8>> {
+        match self {
+            Self::MediaClassPrimaryID(guid) | Self::MediaClassSecondaryID(guid) | Self::WMCollectionID(guid)
+            | Self::WMCollectionGroupID(guid) | Self::WMContentID(guid) => guid_from_string(guid),
+            Self::AverageLevel(level) | Self::PeakValue(level) => Some(level.to_le_bytes().to_vec()),
+            Self::Xmp(xmp) => Some(xmp.as_bytes().to_vec())
+        }
+    }
+}
+
+/// Format a GUID stored with its first 3 fields in little endian, as `{XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX}`
+fn guid_to_string(bytes : &[u8]) -> Option<String> {
+    if bytes.len() != 16 {
+        return None;
+    }
+    let hex = |bytes : &[u8]| bytes.iter().map(|byte| format!("{:02X}", byte)).collect::<String>();
+    let reversed = |bytes : &[u8]| hex(&bytes.iter().rev().copied().collect::<Vec<u8>>());
+    Some(format!(
+        "{{{}-{}-{}-{}-{}}}",
+        reversed(&bytes[0..4]), reversed(&bytes[4..6]), reversed(&bytes[6..8]), hex(&bytes[8..10]), hex(&bytes[10..16])
+    ))
+}
+
+fn guid_from_string(guid : &str) -> Option<Vec<u8>> {
+    let digits = guid.trim_matches(|c| c == '{' || c == '}').replace('-', "");
+    if digits.len() != 32 || !digits.is_ascii() {
+        return None;
+    }
+    let mut bytes = (0..16)
+    .map(|index| u8::from_str_radix(&digits[(2 * index)..(2 * index + 2)], 16).ok())
+    .collect::<Option<Vec<u8>>>()?;
+    bytes[0..4].reverse();
+    bytes[4..6].reverse();
+    bytes[6..8].reverse();
+    Some(bytes)
+}
@@ -0,0 +1,57 @@
+/// Which ReplayGain values are read or written
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReplayGainScope {
+    Track,
+    Album
+}
+
+impl ReplayGainScope {
+    /// Description of the TXXX frame storing the gain
+    pub(crate) fn gain_description(&self) -> &'static str {
+        match self {
+            Self::Track => "REPLAYGAIN_TRACK_GAIN",
+            Self::Album => "REPLAYGAIN_ALBUM_GAIN"
+        }
+    }
+    /// Description of the TXXX frame storing the peak
+    pub(crate) fn peak_description(&self) -> &'static str {
+        match self {
+            Self::Track => "REPLAYGAIN_TRACK_PEAK",
+            Self::Album => "REPLAYGAIN_ALBUM_PEAK"
+        }
+    }
+    /// Identification of the RVA2 frame storing the values
+    pub(crate) fn identification(&self) -> &'static str {
+        match self {
+            Self::Track => "track",
+            Self::Album => "album"
+        }
+    }
+}
+
+/// ReplayGain values of a track or an album
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ReplayGain {
+    /// Gain in decibels
+    pub gain : f32,
+    /// Peak amplitude, 1.0 being the full scale
+    pub peak : Option<f64>
+}
+
+impl ReplayGain {
+    /// Parse a gain written as `-6.48 dB`
+    pub(crate) fn parse_gain(text : &str) -> Option<f32> {
+        let text = text.trim_matches(|c : char| c == '\u{0}' || c.is_whitespace());
+        let text = text.strip_suffix("dB").or_else(|| text.strip_suffix("db")).unwrap_or(text);
+        text.trim().parse().ok()
+    }
+    pub(crate) fn parse_peak(text : &str) -> Option<f64> {
+        text.trim_matches(|c : char| c == '\u{0}' || c.is_whitespace()).parse().ok()
+    }
+    pub(crate) fn gain_text(&self) -> String {
+        format!("{:+.2} dB", self.gain)
+    }
+    pub(crate) fn peak_text(peak : f64) -> String {
+        format!("{:.6}", peak)
+    }
+}
@@ -0,0 +1,320 @@
+//! Serato DJ metadata stored in general encapsulated objects (GEOB)
+//!
+//! Each object is identified by the content description of its GEOB frame and begins with a 2 bytes version
+
+use std::convert::TryInto;
+
+use crate::util::base64;
+
+/// Minimum size of the "Serato Markers2" object, Serato pads it with null bytes
+const MARKERS2_MIN_SIZE : usize = 470;
+/// Length of the lines of the base64 text of the "Serato Markers2" object
+const MARKERS2_LINE_LENGTH : usize = 72;
+const MARKERS2_VERSION : [u8; 2] = [0x01, 0x01];
+const BEATGRID_VERSION : [u8; 2] = [0x01, 0x00];
+const AUTOTAGS_VERSION : [u8; 2] = [0x01, 0x01];
+const OVERVIEW_VERSION : [u8; 2] = [0x01, 0x05];
+
+/// A Serato object stored in a GEOB frame
+pub(crate) trait SeratoObject : Sized {
+    /// Content description of the GEOB frame
+    const DESCRIPTION : &'static str;
+    fn from_bytes(data : &[u8]) -> Option<Self>;
+    fn to_bytes(&self) -> Vec<u8>;
+}
+
+fn color_to_bytes(color : u32) -> [u8; 3] {
+    let bytes = color.to_be_bytes();
+    [bytes[1], bytes[2], bytes[3]]
+}
+
+fn color_from_bytes(bytes : &[u8]) -> u32 {
+    u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]])
+}
+
+/// Split the null terminated UTF-8 string at the beginning of `bytes`
+fn split_string(bytes : &[u8]) -> Option<(String, &[u8])> {
+    let end = bytes.iter().position(|byte| *byte == 0)?;
+    Some((String::from_utf8_lossy(&bytes[..end]).into_owned(), &bytes[(end + 1)..]))
+}
+
+/// A hot cue of the "Serato Markers2" object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeratoCue {
+    /// Index of the cue, from 0
+    pub index : u8,
+    /// Position in milliseconds
+    pub position : u32,
+    /// Color as 0xRRGGBB
+    pub color : u32,
+    pub name : String
+}
+
+/// A saved loop of the "Serato Markers2" object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeratoLoop {
+    /// Index of the loop, from 0
+    pub index : u8,
+    /// Start position in milliseconds
+    pub start : u32,
+    /// End position in milliseconds
+    pub end : u32,
+    /// Color as 0xRRGGBB
+    pub color : u32,
+    pub locked : bool,
+    pub name : String
+}
+
+/// Cue points, loops and colors of a track ("Serato Markers2")
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SeratoMarkers2 {
+    /// Color of the track as 0xRRGGBB
+    pub track_color : Option<u32>,
+    pub cues : Vec<SeratoCue>,
+    pub loops : Vec<SeratoLoop>,
+    /// Whether the BPM is locked
+    pub bpm_lock : Option<bool>,
+    /// The entries which aren't decoded, such as the flips, as (type, data)
+    pub others : Vec<(String, Vec<u8>)>
+}
+
+impl SeratoMarkers2 {
+    /// Content description of the GEOB frame
+    pub const DESCRIPTION : &'static str = "Serato Markers2";
+
+    fn decode_entry(&mut self, name : &str, data : &[u8]) -> Option<()> {
+        match name {
+            "COLOR" => self.track_color = Some(color_from_bytes(data.get(1..4)?)),
+            "BPMLOCK" => self.bpm_lock = Some(*data.first()? != 0),
+            "CUE" => {
+                let (name, _) = split_string(data.get(12..)?)?;
+                self.cues.push(SeratoCue {
+                    index: data[1],
+                    position: u32::from_be_bytes(data[2..6].try_into().ok()?),
+                    color: color_from_bytes(&data[7..10]),
+                    name
+                })
+            }
+            "LOOP" => {
+                let (name, _) = split_string(data.get(20..)?)?;
+                self.loops.push(SeratoLoop {
+                    index: data[1],
+                    start: u32::from_be_bytes(data[2..6].try_into().ok()?),
+                    end: u32::from_be_bytes(data[6..10].try_into().ok()?),
+                    color: color_from_bytes(&data[15..18]),
+                    locked: data[19] != 0,
+                    name
+                })
+            }
+            _ => return None
+        }
+        Some(())
+    }
+
+    fn entries(&self) -> Vec<(&str, Vec<u8>)> {
+        let mut entries = vec![];
+        if let Some(color) = self.track_color {
+            let mut data = vec![0];
+            data.extend_from_slice(&color_to_bytes(color));
+            entries.push(("COLOR", data))
+        }
+        for cue in self.cues.iter() {
+            let mut data = vec![0, cue.index];
+            data.extend_from_slice(&cue.position.to_be_bytes());
+            data.push(0);
+            data.extend_from_slice(&color_to_bytes(cue.color));
+            data.extend_from_slice(&[0, 0]);
+            data.extend_from_slice(cue.name.as_bytes());
+            data.push(0);
+            entries.push(("CUE", data))
+        }
+        for serato_loop in self.loops.iter() {
+            let mut data = vec![0, serato_loop.index];
+            data.extend_from_slice(&serato_loop.start.to_be_bytes());
+            data.extend_from_slice(&serato_loop.end.to_be_bytes());
+            data.extend_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF, 0]);
+            data.extend_from_slice(&color_to_bytes(serato_loop.color));
+            data.extend_from_slice(&[0, serato_loop.locked as u8]);
+            data.extend_from_slice(serato_loop.name.as_bytes());
+            data.push(0);
+            entries.push(("LOOP", data))
+        }
+        for (name, data) in self.others.iter() {
+            entries.push((name, data.clone()))
+        }
+        if let Some(bpm_lock) = self.bpm_lock {
+            entries.push(("BPMLOCK", vec![bpm_lock as u8]))
+        }
+        entries
+    }
+}
+
+impl SeratoObject for SeratoMarkers2 {
+    const DESCRIPTION : &'static str = SeratoMarkers2::DESCRIPTION;
+
+    fn from_bytes(data : &[u8]) -> Option<Self> {
+        let text = data.strip_prefix(&MARKERS2_VERSION)?;
+        let end = text.iter().position(|byte| *byte == 0).unwrap_or(text.len());
+        let payload = base64::decode(&text[..end])?;
+        let mut entries = payload.strip_prefix(&MARKERS2_VERSION)?;
+        let mut markers = Self::default();
+        while let Some((name, rest)) = split_string(entries) {
+            if name.is_empty() {
+                break;
+            }
+            let length = u32::from_be_bytes(rest.get(0..4)?.try_into().ok()?) as usize;
+            let data = rest.get(4..(4 + length))?;
+            if markers.decode_entry(&name, data).is_none() {
+                markers.others.push((name, data.to_vec()))
+            }
+            entries = &rest[(4 + length)..];
+        }
+        Some(markers)
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut payload = MARKERS2_VERSION.to_vec();
+        for (name, data) in self.entries() {
+            payload.extend_from_slice(name.as_bytes());
+            payload.push(0);
+            payload.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            payload.extend_from_slice(&data);
+        }
+        payload.push(0);
+        let text = base64::encode(&payload, false);
+        let mut bytes = MARKERS2_VERSION.to_vec();
+        for (index, line) in text.as_bytes().chunks(MARKERS2_LINE_LENGTH).enumerate() {
+            if index > 0 {
+                bytes.push(b'\n')
+            }
+            bytes.extend_from_slice(line)
+        }
+        bytes.push(0);
+        bytes.resize(bytes.len().max(MARKERS2_MIN_SIZE), 0);
+        bytes
+    }
+}
+
+/// Beat grid of a track ("Serato BeatGrid")
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SeratoBeatGrid {
+    /// The markers followed by another marker, as (position in seconds, number of beats until the next marker)
+    pub markers : Vec<(f32, u32)>,
+    /// The last marker, as (position in seconds, BPM)
+    pub terminal_marker : Option<(f32, f32)>
+}
+
+impl SeratoBeatGrid {
+    /// Content description of the GEOB frame
+    pub const DESCRIPTION : &'static str = "Serato BeatGrid";
+}
+
+impl SeratoObject for SeratoBeatGrid {
+    const DESCRIPTION : &'static str = SeratoBeatGrid::DESCRIPTION;
+
+    fn from_bytes(data : &[u8]) -> Option<Self> {
+        let data = data.strip_prefix(&BEATGRID_VERSION)?;
+        let count = u32::from_be_bytes(data.get(0..4)?.try_into().ok()?) as usize;
+        let markers = data.get(4..)?.chunks_exact(8).take(count).collect::<Vec<&[u8]>>();
+        if markers.len() != count {
+            return None;
+        }
+        let position = |marker : &[u8]| f32::from_be_bytes([marker[0], marker[1], marker[2], marker[3]]);
+        let terminal_marker = markers.last().map(|marker| {
+            (position(marker), f32::from_be_bytes([marker[4], marker[5], marker[6], marker[7]]))
+        });
+        let markers = markers[..count.saturating_sub(1)].iter()
+        .map(|marker| (position(marker), u32::from_be_bytes([marker[4], marker[5], marker[6], marker[7]])))
+        .collect();
+        Some(Self { markers, terminal_marker })
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = BEATGRID_VERSION.to_vec();
+        let count = self.terminal_marker.map_or(0, |_| self.markers.len() + 1);
+        bytes.extend_from_slice(&(count as u32).to_be_bytes());
+        if let Some((last_position, bpm)) = self.terminal_marker {
+            for (position, beats) in self.markers.iter() {
+                bytes.extend_from_slice(&position.to_be_bytes());
+                bytes.extend_from_slice(&beats.to_be_bytes());
+            }
+            bytes.extend_from_slice(&last_position.to_be_bytes());
+            bytes.extend_from_slice(&bpm.to_be_bytes());
+        }
+        // Footer
+        bytes.push(0);
+        bytes
+    }
+}
+
+/// Analysis values of a track ("Serato Autotags")
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct SeratoAutotags {
+    pub bpm : f32,
+    /// Gain computed by the analysis in decibels
+    pub auto_gain : f32,
+    /// Gain set by the user in decibels
+    pub gain : f32
+}
+
+impl SeratoAutotags {
+    /// Content description of the GEOB frame
+    pub const DESCRIPTION : &'static str = "Serato Autotags";
+}
+
+impl SeratoObject for SeratoAutotags {
+    const DESCRIPTION : &'static str = SeratoAutotags::DESCRIPTION;
+
+    fn from_bytes(data : &[u8]) -> Option<Self> {
+        let data = data.strip_prefix(&AUTOTAGS_VERSION)?;
+        let (bpm, data) = split_string(data)?;
+        let (auto_gain, data) = split_string(data)?;
+        let (gain, _) = split_string(data)?;
+        Some(Self {
+            bpm: bpm.trim().parse().ok()?,
+            auto_gain: auto_gain.trim().parse().ok()?,
+            gain: gain.trim().parse().ok()?
+        })
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = AUTOTAGS_VERSION.to_vec();
+        for value in [format!("{:.2}", self.bpm), format!("{:.3}", self.auto_gain), format!("{:.3}", self.gain)] {
+            bytes.extend_from_slice(value.as_bytes());
+            bytes.push(0)
+        }
+        bytes
+    }
+}
+
+/// Waveform overview of a track ("Serato Overview")
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SeratoOverview {
+    /// The columns of the waveform, from the beginning of the track
+    pub blocks : Vec<[u8; 16]>
+}
+
+impl SeratoOverview {
+    /// Content description of the GEOB frame
+    pub const DESCRIPTION : &'static str = "Serato Overview";
+}
+
+impl SeratoObject for SeratoOverview {
+    const DESCRIPTION : &'static str = SeratoOverview::DESCRIPTION;
+
+    fn from_bytes(data : &[u8]) -> Option<Self> {
+        let data = data.strip_prefix(&OVERVIEW_VERSION)?;
+        let blocks = data.chunks_exact(16)
+        .map(|block| block.try_into().unwrap_or_default())
+        .collect();
+        Some(Self { blocks })
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = OVERVIEW_VERSION.to_vec();
+        for block in self.blocks.iter() {
+            bytes.extend_from_slice(block)
+        }
+        bytes
+    }
+}
@@ -1,25 +1,49 @@
+use std::cell::Cell;
 use std::fs::{File, OpenOptions};
-use std::io::{Error, Read, Write};
+use std::io::{Error, Read, Seek, SeekFrom, Write};
 
+use super::code::event_timing_code::event_type::EventType;
+use super::code::event_timing_code::time_stamp_format::TimeStampFormat;
 use super::code::picture_code::picture_type::PictureType;
-use super::id3_frame_value::{
-    AttachedPictureFrame, CommentFrame, FrameValue, TextFrame, UnsyncLyricsFrame,
+use super::code::volume_code::channel_type::ChannelType;
+use super::id3_replay_gain::{ReplayGain, ReplayGainScope};
+use super::id3_serato::{SeratoAutotags, SeratoBeatGrid, SeratoMarkers2, SeratoObject, SeratoOverview};
+use super::code::rating_code::rating_scale::RatingScale;
+use super::id3_frame_value::{people_from_strings, 
+    AttachedPictureFrame, CommentFrame, Equalisation2Frame, EqualiisationFrame, EventTimingFrame, FrameValue,
+    GeneralEncapsulatedObjectFrame, InvolvedPeopleFrame, LinkedInfoFrame, MusicCdIdframe, PositionSyncFrame, PrivateFrame, ReverbFrame,
+    LocationLookupTableFrame, PlayCounterFrame, PopularimeterFrame, RelativeVolumeAdjustementFrame,
+    SyncLyricsFrame, SyncTempoCodeFrame, TextFrame, UniqueFileIdentifierFrame, UnsyncLyricsFrame, UrlFrame, UserUrlFrame, UserInfoFrame, ChannelAdjustment,
 };
-use super::id3_frameid::ID3FRAMEID;
+use super::id3_frameid::{ID3FRAMEID, ID3TEXTFRAMEID, ID3URLFRAMEID};
+use crate::id3::code::genre_code::genre::{genre_index, NO_GENRE};
+use crate::id3::id3_extended_header::ID3ExtendedHeader;
 use crate::id3::id3_frame::ID3FRAME;
+use crate::id3::id3_padding::{PaddingPolicy, WriteStrategy};
 use crate::id3::id3_frameid::ID3FRAMEID::*;
 use crate::id3::id3_frameid::ID3TEXTFRAMEID::*;
-use crate::id3::id3_header_flag::ID3HeaderFLAG;
+use crate::id3::id3_header_flag::{FrameStatusFlags, ID3HeaderFLAG};
 use crate::id3::id3_header_flag::ID3HeaderFLAG::{
-    ExperimentalIndicator, ExtendedHeader, Unsynchronisation,
+    ExperimentalIndicator, ExtendedHeader, FooterPresent, Unsynchronisation,
 };
+use crate::id3::id3_version::ID3Version;
+use crate::id3::id3v1_tag::{ID3v1Mode, ID3v1TAG};
 use crate::tag_error::TagError;
+use crate::util::cd_toc::CdToc;
+use crate::util::crc::crc32;
 use crate::util::file_format::PictureFormat;
-use crate::util::function::{synchsafe, unsynchsafe};
-use crate::util::traits::{FrameSize, TagSize};
+use crate::util::function::{deunsynchronise, synchsafe, unsynchronise, unsynchsafe};
+use crate::util::mpeg::mpeg_frames;
+use crate::util::traits::{FrameSize, RawSize, TagSize};
 
 
+/// Compression bit of the ID3v2.2 header flags
+const V22_COMPRESSION_FLAG : u8 = 0b01_000_000;
+
 /// Metadata for mp3 file
+///
+/// ID3v2.2 tags are read and written back as ID3v2.3.
+/// An ID3v1 tag at the end of the file is read as well, see [ID3TAG::set_id3v1_mode]
 pub struct ID3TAG {
     file_path: String,
     _identifier: String,
@@ -28,8 +52,20 @@ pub struct ID3TAG {
     size: u32,
     _flags_header: Vec<ID3HeaderFLAG>,
     frames: Vec<ID3FRAME>,
-    padding: i32,
-    music_data : Vec<u8>
+    /// Size of the tag in the file it has been read from (header, padding and footer included)
+    original_tag_size : Option<usize>,
+    /// Size of the ID3v1 tag in the file it has been read from
+    original_id3v1_size : usize,
+    padding_policy : PaddingPolicy,
+    extended_header : Option<ID3ExtendedHeader>,
+    crc_check : Option<bool>,
+    compression_threshold : Option<usize>,
+    /// Whether the tag has been edited since it was read
+    altered : bool,
+    force_read_only_edits : bool,
+    music_data : Vec<u8>,
+    id3v1 : Option<ID3v1TAG>,
+    id3v1_mode : ID3v1Mode
 }
 
 impl ID3TAG {
@@ -44,9 +80,9 @@ impl ID3TAG {
         if buffer.len() <= 10 {
             return Err(());
         }
-        let id = String::from_utf8(buffer.drain(0..3).collect()).unwrap();
+        let id = String::from_utf8(buffer.drain(0..3).collect()).map_err(|_| ())?;
         let major_version = buffer.remove(0);
-        if major_version != 3 {
+        if id != "ID3" || (major_version != 2 && ID3Version::from_raw_value(major_version).is_none()) {
             return Err(());
         }
         let _minor_version = buffer.remove(0);
@@ -56,12 +92,19 @@ impl ID3TAG {
         if (flag & (Unsynchronisation as u8)) == (Unsynchronisation as u8) {
             _flags_header.push(Unsynchronisation)
         };
-        if (flag & (ExtendedHeader as u8)) == (ExtendedHeader as u8) {
+        if major_version == 2 && (flag & V22_COMPRESSION_FLAG) == V22_COMPRESSION_FLAG {
+            // No compression scheme was ever defined for ID3v2.2
+            return Err(());
+        }
+        if major_version > 2 && (flag & (ExtendedHeader as u8)) == (ExtendedHeader as u8) {
             _flags_header.push(ExtendedHeader)
         };
         if (flag & (ExperimentalIndicator as u8)) == (ExperimentalIndicator as u8) {
             _flags_header.push(ExperimentalIndicator)
         };
+        if major_version == 4 && (flag & (FooterPresent as u8)) == (FooterPresent as u8) {
+            _flags_header.push(FooterPresent)
+        };
         let buf = buffer.drain(0..4).collect::<Vec<u8>>();
         let size_from_buffer = u32::from_be_bytes([
             buf.get(0).unwrap().clone(),
@@ -69,24 +112,55 @@ impl ID3TAG {
             buf.get(2).unwrap().clone(),
             buf.get(3).unwrap().clone(),
         ]);
-        let size = if !_flags_header.contains(&Unsynchronisation) {
-            unsynchsafe(size_from_buffer)
+        let size = unsynchsafe(size_from_buffer);
+        if size as usize > buffer.len() {
+            return Err(());
+        }
+        let mut music_data : Vec<u8> = buffer.drain((size as usize)..).collect();
+        if _flags_header.contains(&FooterPresent) {
+            music_data.drain(0..(10.min(music_data.len())));
+        }
+        let original_tag_size = 10 + size as usize + if _flags_header.contains(&FooterPresent) { 10 } else { 0 };
+        let (id3v1, original_id3v1_size) = match ID3v1TAG::from_trailing_bytes(&music_data) {
+            Some((id3v1, id3v1_size)) => {
+                music_data.truncate(music_data.len() - id3v1_size);
+                (Some(id3v1), id3v1_size)
+            }
+            None => (None, 0)
+        };
+        let unsynchronised = _flags_header.contains(&Unsynchronisation);
+        if unsynchronised && major_version < 4 {
+            // Before ID3v2.4, the unsynchronisation is applied to the whole tag
+            *buffer = deunsynchronise(buffer);
+        }
+        let mut extended_header = None;
+        let mut crc_check = None;
+        if _flags_header.contains(&ExtendedHeader) {
+            let header = ID3ExtendedHeader::from_buffer(buffer, major_version).ok_or(())?;
+            if let Some(crc) = header.crc() {
+                // ID3v2.3 : CRC of the frames, ID3v2.4 : CRC of the frames and the padding
+                let end = if major_version == 4 {
+                    buffer.len()
+                } else {
+                    buffer.len().saturating_sub(header.padding_size() as usize)
+                };
+                crc_check = Some(crc32(&buffer[0..end]) == crc);
+            }
+            extended_header = Some(header);
+        }
+        while let Some(frame) = ID3FRAME::new(buffer, major_version, unsynchronised) {
+            frames.push(frame);
+        }
+
+        // The footer is never written back
+        _flags_header.retain(|flag| *flag != FooterPresent);
+        // ID3v2.2 tags are written back as ID3v2.3
+        let major_version = if major_version == 2 {
+            frames.iter_mut().for_each(|frame| frame.adapt_to_version(3));
+            3
         } else {
-            size_from_buffer
+            major_version
         };
-        let music_data = buffer.drain((size as usize - 10)..).collect();
-        while buffer.len() > 10 {
-            if let Some(frame) = ID3FRAME::new(buffer) {
-                frames.push(frame);
-                //println!()
-            } else {
-                //println!("No ID3FRAME");
-                //println!("Padding size : {} ", &buffer.len());
-                break;
-            }
-        }
-        
-        let padding = buffer.len() as i32 + 4;
         let mut tag = Self {
             file_path: file_path.into(),
             _identifier: id,
@@ -95,29 +169,84 @@ impl ID3TAG {
             size,
             _flags_header,
             frames,
-            padding,
-            music_data
+            original_tag_size: Some(original_tag_size),
+            original_id3v1_size,
+            padding_policy: PaddingPolicy::default(),
+            extended_header,
+            crc_check,
+            compression_threshold: None,
+            altered: false,
+            force_read_only_edits: false,
+            music_data,
+            id3v1,
+            id3v1_mode: ID3v1Mode::default()
         };
         tag.recalcule_all_size();
         Ok(tag)
     }
 
     pub(crate) fn new_empty_tag() -> Self {
-        let padding_size = 10_000;
+        let padding_policy = PaddingPolicy::default();
         Self {
             file_path: "".into(),
             _identifier: "ID3".into(),
             major_version: 3,
             _minor_version: 0,
-            size: padding_size + 10,
+            size: (padding_policy.padding(10) + 10) as u32,
             _flags_header: vec![],
             frames: vec![],
-            padding: padding_size as i32,
-            music_data: vec![]
+            original_tag_size: None,
+            original_id3v1_size: 0,
+            padding_policy,
+            extended_header: None,
+            crc_check: None,
+            compression_threshold: None,
+            altered: false,
+            force_read_only_edits: false,
+            music_data: vec![],
+            id3v1: None,
+            id3v1_mode: ID3v1Mode::default()
         }
     }
 
     pub(crate) fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.id3v2_bytes();
+        bytes.append(&mut self.music_data.clone());
+        bytes.append(&mut self.id3v1_bytes());
+        bytes
+    }
+
+    /// Returns the ID3v2 tag bytes with its padding
+    ///
+    /// The tag keeps the size it had in the file if the frames still fit,
+    /// otherwise the padding follows the padding policy
+    fn id3v2_bytes(&self) -> Vec<u8> {
+        let frames = self.frames_bytes();
+        let tag_size = self.header_and_body_bytes(&frames, 0).len();
+        self.header_and_body_bytes(&frames, self.padding_for(tag_size))
+    }
+
+    fn padding_for(&self, tag_size : usize) -> usize {
+        match self.original_tag_size {
+            Some(original_size) if tag_size <= original_size => original_size - tag_size,
+            _ => self.padding_policy.padding(tag_size)
+        }
+    }
+
+    fn frames_bytes(&self) -> Vec<u8> {
+        let unsynchronised = self._flags_header.contains(&Unsynchronisation);
+        let mut frames = vec![];
+        self.frames
+            .iter()
+            .filter(|frame| self.is_written(frame))
+            .for_each(|frame| {
+                let compress = self.compression_threshold.is_some_and(|threshold| frame.should_compress(threshold));
+                frames.append(&mut frame.as_bytes(self.major_version, unsynchronised, compress))
+            });
+        frames
+    }
+
+    fn header_and_body_bytes(&self, frames : &[u8], padding : usize) -> Vec<u8> {
         let mut bytes = vec![];
 
         bytes.append(&mut self._identifier.clone().into_bytes());
@@ -128,16 +257,50 @@ impl ID3TAG {
             flags |= *flag as u8
         }
         bytes.push(flags);
-        let mut sync = synchsafe(self.size).to_be_bytes().to_vec();
+        let unsynchronised = self._flags_header.contains(&Unsynchronisation);
+        let mut padding = vec![0u8; padding];
+        let mut body = match self.extended_header.as_ref() {
+            Some(header) => {
+                let crc = if self.major_version == 4 {
+                    crc32(&[frames, padding.as_slice()].concat())
+                } else {
+                    crc32(frames)
+                };
+                header.as_bytes(self.major_version, padding.len() as u32, crc)
+            }
+            None => vec![]
+        };
+        body.extend_from_slice(frames);
+        body.append(&mut padding);
+        if unsynchronised && self.major_version < 4 {
+            body = unsynchronise(&body);
+        }
+        let mut sync = synchsafe(body.len() as u32).to_be_bytes().to_vec();
         bytes.append(&mut sync);
-
-        self.frames
-            .iter()
-            .for_each(|frame| bytes.append(&mut frame.as_bytes()));
-        bytes.append(&mut vec![0u8; self.padding as usize]);
-        bytes.append(&mut self.music_data.clone());
+        bytes.append(&mut body);
         bytes
     }
+
+    fn id3v1_bytes(&self) -> Vec<u8> {
+        match self.id3v1_mode {
+            ID3v1Mode::Keep => self.id3v1.as_ref().map(|id3v1| id3v1.as_bytes()).unwrap_or_default(),
+            ID3v1Mode::Sync => self.synced_id3v1().as_bytes(),
+            ID3v1Mode::Strip => vec![]
+        }
+    }
+}
+
+/// Size of the ID3v2 tag at the beginning of `file` (header, padding and footer included)
+fn on_disk_tag_size(file : &mut File) -> Result<Option<usize>, Error> {
+    let mut header = [0u8; 10];
+    file.seek(SeekFrom::Start(0))?;
+    file.read_exact(&mut header)?;
+    if &header[0..3] != b"ID3" {
+        return Ok(None);
+    }
+    let size = unsynchsafe(u32::from_be_bytes([header[6], header[7], header[8], header[9]])) as usize;
+    let footer = header[3] == 4 && (header[5] & (FooterPresent as u8)) != 0;
+    Ok(Some(10 + size + if footer { 10 } else { 0 }))
 }
 
 impl TagSize for ID3TAG {
@@ -151,26 +314,44 @@ impl ID3TAG {
         self.frames
             .iter_mut()
             .for_each(|frame| frame.recalcule_size());
-        self.recalcule_size()
+        self.update_size()
     }
 
+    /// Recompute the size of the tag after a frame edit, which counts as a tag alteration
     pub fn recalcule_size(&mut self) {
+        self.altered = true;
+        self.update_size()
+    }
+
+    /// Recompute the size of the tag without marking it as altered
+    fn update_size(&mut self) {
         //self.size = self.frame_total_size() + (self.padding as u32)
-        self.size = self.total_size()
+        self.size = self.total_size();
     }
 
     pub fn total_size(&self) -> u32 {
-        self.frame_total_size() + (self.padding as u32) + 10
+        let extended_header_size = self.extended_header
+            .as_ref()
+            .map(|header| header.size(self.major_version))
+            .unwrap_or(0);
+        let tag_size = self.frame_total_size() + extended_header_size + 10;
+        tag_size + self.padding_for(tag_size as usize) as u32
     }
 
     pub fn frame_total_size(&self) -> u32 {
         let mut size = 0u32;
-        for frame in self.frames.iter() {
+        for frame in self.frames.iter().filter(|frame| self.is_written(frame)) {
             size += frame.total_size()
         }
         size
     }
 
+    /// Whether the frame is written, the frames flagged to be discarded on tag alteration are dropped
+    /// once the tag has been altered
+    fn is_written(&self, frame: &ID3FRAME) -> bool {
+        !(self.altered && frame.status_flags().discard_on_tag_alteration)
+    }
+
     pub(crate) fn get_text_from_text_frame(&self, frame_id: &ID3FRAMEID) -> Option<String> {
         self.frames
             .iter()
@@ -195,10 +376,15 @@ impl ID3TAG {
     pub(crate) fn set_text_frame(&mut self, frame_id: ID3FRAMEID, text: String) {
         let major_version = self.major_version.clone();
 
+        let force_read_only_edits = self.force_read_only_edits;
         if let Some(frame) = self.get_frame_mut(&frame_id) {
+            if frame.is_read_only() && !force_read_only_edits {
+                return;
+            }
             let text_frame = frame.as_text_frame_mut().unwrap();
             text_frame.set_text(text, major_version);
-            frame.recalcule_size()
+            // The frame has been rewritten, it mustn't be discarded because the tag changed
+            frame.mark_rewritten()
         } else {
             let value = TextFrame::new(major_version, text);
             let frame = (frame_id, FrameValue::TF(value)).into();
@@ -208,9 +394,37 @@ impl ID3TAG {
         self.recalcule_size();
     }
 
+    /// Remove the frames `frame_id`, except the read only ones unless the edits are forced
     pub(crate) fn remove_frames(&mut self, frame_id: &ID3FRAMEID) {
-        self.frames.retain(|frame| frame.get_frame_id() != frame_id);
+        self.remove_frames_matching(|frame| frame.get_frame_id() == frame_id);
+    }
+
+    /// Remove the frames matching `predicate`, except the read only ones unless the edits are forced
+    ///
+    /// Returns `true` if a frame has been removed
+    pub(crate) fn remove_frames_matching<P: Fn(&ID3FRAME) -> bool>(&mut self, predicate: P) -> bool {
+        let force_read_only_edits = self.force_read_only_edits;
+        let count = self.frames.len();
+        self.frames.retain(|frame| !predicate(frame) || (frame.is_read_only() && !force_read_only_edits));
+        if self.frames.len() == count {
+            return false;
+        }
         self.recalcule_size();
+        true
+    }
+
+    /// Returns the first frame matching `predicate`
+    ///
+    /// `Err` if this frame is read only and the edits aren't forced
+    pub(crate) fn find_frame_mut<P: Fn(&ID3FRAME) -> bool>(
+        &mut self,
+        predicate: P,
+    ) -> Result<Option<&mut ID3FRAME>, ()> {
+        let force_read_only_edits = self.force_read_only_edits;
+        match self.frames.iter_mut().find(|frame| predicate(frame)) {
+            Some(frame) if frame.is_read_only() && !force_read_only_edits => Err(()),
+            frame => Ok(frame),
+        }
     }
 
     pub(crate) fn get_unsynch_lyrics(&self) -> Vec<String> {
@@ -322,6 +536,78 @@ impl ID3TAG {
         }
     }
 
+    /// Replace the value of the first frame matching `predicate`, or add the frame `frame_id` if there is none
+    ///
+    /// Returns `false` if the frame is read only and the edits aren't forced
+    pub(crate) fn replace_frame<P: Fn(&ID3FRAME) -> bool>(
+        &mut self,
+        frame_id: ID3FRAMEID,
+        value: FrameValue,
+        predicate: P,
+    ) -> bool {
+        match self.find_frame_mut(predicate) {
+            Err(()) => return false,
+            Ok(Some(frame)) => {
+                *frame.get_frame_value_mut() = value;
+                frame.mark_rewritten()
+            }
+            Ok(None) => self.frames.push((frame_id, value).into()),
+        }
+        self.recalcule_size();
+        true
+    }
+
+    /// Returns the text of the user defined text frame (TXXX) `description`, compared case insensitively
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::ID3TAG;
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// metadata.set_user_text("CATALOGNUMBER", "WARPCD123");
+    /// assert_eq!(metadata.get_user_text("catalognumber").unwrap(), "WARPCD123")
+    /// ```
+    pub fn get_user_text(&self, description: &str) -> Option<String> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.as_user_info_frame())
+            .find(|f| same_description(f.get_description(), description))
+            .map(|f| f.get_text().to_string())
+    }
+
+    /// Returns the (description, text) of all the user defined text frames (TXXX)
+    pub fn user_texts(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.as_user_info_frame())
+            .map(|f| (f.get_description(), f.get_text()))
+    }
+
+    /// Set the text of the user defined text frame (TXXX) `description`, compared case insensitively.
+    /// The other frames with the same description are removed
+    ///
+    /// Returns `false` if the frame is read only
+    pub fn set_user_text(&mut self, description: &str, text: &str) -> bool {
+        let value = FrameValue::UIF(UserInfoFrame::new(self.major_version, description.into(), text.into()));
+        let is_description = |frame: &ID3FRAME| {
+            frame.as_user_info_frame().is_some_and(|f| same_description(f.get_description(), description))
+        };
+        if !self.replace_frame(TXXX, value, is_description) {
+            return false;
+        }
+        let is_first = Cell::new(true);
+        self.remove_frames_matching(|frame| is_description(frame) && !is_first.replace(false));
+        true
+    }
+
+    /// Remove the user defined text frames (TXXX) `description`, compared case insensitively
+    ///
+    /// Returns `false` if no frame was removed
+    pub fn remove_user_text(&mut self, description: &str) -> bool {
+        self.remove_frames_matching(|frame| {
+            frame.as_user_info_frame().is_some_and(|f| same_description(f.get_description(), description))
+        })
+    }
+
     pub(crate) fn get_frame_mut(&mut self, frame_id: &ID3FRAMEID) -> Option<&mut ID3FRAME> {
         self.frames
             .iter_mut()
@@ -329,11 +615,391 @@ impl ID3TAG {
     }
 }
 
+impl ID3TAG {
+    /// Move the date frames (TYER, TDAT, TIME, TORY), the involved people list (IPLS), the volume
+    /// adjustment (RVAD) and the equalisation (EQUA) to their ID3v2.4 counterparts (TDRC, TDOR, TIPL, RVA2, EQU2)
+    fn upgrade_frames(&mut self) {
+        if let Some(year) = self.get_text_from_text_frame(&TEXTFRAME(TYER)) {
+            let mut recording_time = year.trim_end_matches('\u{0}').to_string();
+            let date = self.get_text_from_text_frame(&TEXTFRAME(TDAT)).unwrap_or_default();
+            if let (Some(day), Some(month)) = (date.get(0..2), date.get(2..4)) {
+                recording_time.push_str(&format!("-{}-{}", month, day));
+                let time = self.get_text_from_text_frame(&TEXTFRAME(TIME)).unwrap_or_default();
+                if let (Some(hour), Some(minute)) = (time.get(0..2), time.get(2..4)) {
+                    recording_time.push_str(&format!("T{}:{}", hour, minute))
+                }
+            }
+            if self.get_text_from_text_frame(&TEXTFRAME(TDRC)).is_none() {
+                self.set_text_frame(TEXTFRAME(TDRC), recording_time)
+            }
+        }
+        if let Some(original_year) = self.get_text_from_text_frame(&TEXTFRAME(TORY)) {
+            if self.get_text_from_text_frame(&TEXTFRAME(TDOR)).is_none() {
+                self.set_text_frame(TEXTFRAME(TDOR), original_year)
+            }
+        }
+        let people = self.people_list(&IPLS);
+        if !people.is_empty() {
+            let mut involved_people = self.people_list(&TEXTFRAME(TIPL));
+            involved_people.extend(people);
+            self.set_people_list(TEXTFRAME(TIPL), involved_people);
+            self.remove_frames(&IPLS)
+        }
+        // RVAD : the volume adjustment of the track
+        if let Some(mut volume) = self.frames.iter().find(|frame| frame.get_frame_id() == &RVAD)
+            .and_then(|frame| frame.as_relative_volume_frame()).cloned() {
+            let identification = ReplayGainScope::Track.identification();
+            if !self.relative_volume_adjustments().iter().any(|f| f.identification() == Some(identification)) {
+                volume.set_identification(Some(identification.into()));
+                self.frames.push((RVA2, FrameValue::RVAF(volume)).into())
+            }
+            self.remove_frames(&RVAD)
+        }
+        // EQUA : the equalisation without identification
+        if let Some(equalisation) = self.equalisation().cloned() {
+            if !self.equalisations2().iter().any(|f| f.identification().is_empty()) {
+                self.frames.push((EQU2, FrameValue::E2F(equalisation.to_equalisation2())).into())
+            }
+            self.remove_frames(&EQUA)
+        }
+        self.frames.retain(|frame| frame.get_frame_id().is_supported_by(ID3Version::V2_4));
+    }
+
+    /// Move the ID3v2.4 frames TDRC, TDOR, TIPL, TMCL, RVA2 and EQU2 to their ID3v2.3 counterparts
+    /// and drop the other ID3v2.4 frames
+    fn downgrade_frames(&mut self) {
+        if let Some(recording_time) = self.get_text_from_text_frame(&TEXTFRAME(TDRC)) {
+            let recording_time = recording_time.trim_end_matches('\u{0}').to_string();
+            if let Some(year) = recording_time.get(0..4) {
+                self.set_text_frame(TEXTFRAME(TYER), year.to_string())
+            }
+            if let (Some(month), Some(day)) = (recording_time.get(5..7), recording_time.get(8..10)) {
+                self.set_text_frame(TEXTFRAME(TDAT), format!("{}{}", day, month))
+            }
+            if let (Some(hour), Some(minute)) = (recording_time.get(11..13), recording_time.get(14..16)) {
+                self.set_text_frame(TEXTFRAME(TIME), format!("{}{}", hour, minute))
+            }
+            self.remove_frames(&TEXTFRAME(TDRC))
+        }
+        if let Some(original_time) = self.get_text_from_text_frame(&TEXTFRAME(TDOR)) {
+            if let Some(original_year) = original_time.get(0..4) {
+                self.set_text_frame(TEXTFRAME(TORY), original_year.to_string())
+            }
+            self.remove_frames(&TEXTFRAME(TDOR))
+        }
+        // The involved people and the musicians share the IPLS frame
+        let mut people = self.people_list(&TEXTFRAME(TIPL));
+        people.extend(self.people_list(&TEXTFRAME(TMCL)));
+        if !people.is_empty() {
+            let mut involved_people = self.people_list(&IPLS);
+            involved_people.extend(people);
+            self.set_people_list(IPLS, involved_people);
+            self.remove_frames(&TEXTFRAME(TIPL));
+            self.remove_frames(&TEXTFRAME(TMCL))
+        }
+        // A single RVAD frame : the track adjustment, or else the first RVA2 frame
+        let volumes = self.relative_volume_adjustments();
+        let volume = volumes.iter()
+            .find(|f| f.identification() == Some(ReplayGainScope::Track.identification()))
+            .or(volumes.first())
+            .map(|f| (*f).clone());
+        if let Some(mut volume) = volume {
+            if !self.frames.iter().any(|frame| frame.get_frame_id() == &RVAD) {
+                volume.set_identification(None);
+                self.frames.push((RVAD, FrameValue::RVAF(volume)).into())
+            }
+            self.remove_frames(&RVA2)
+        }
+        // A single EQUA frame : the equalisation without identification, or else the first EQU2 frame
+        let equalisations = self.equalisations2();
+        let equalisation = equalisations.iter()
+            .find(|f| f.identification().is_empty())
+            .or(equalisations.first())
+            .map(|f| EqualiisationFrame::from_equalisation2(f));
+        if let Some(equalisation) = equalisation {
+            if !self.frames.iter().any(|frame| frame.get_frame_id() == &EQUA) {
+                self.frames.push((EQUA, FrameValue::EF(equalisation)).into())
+            }
+            self.remove_frames(&EQU2)
+        }
+        self.frames.retain(|frame| frame.get_frame_id().is_supported_by(ID3Version::V2_3));
+    }
+
+    /// Whether the unsynchronisation scheme is applied when the tag is written
+    pub fn is_unsynchronised(&self) -> bool {
+        self._flags_header.contains(&Unsynchronisation)
+    }
+
+    /// Apply the unsynchronisation scheme when the tag is written.
+    ///
+    /// The tag won't contain false MPEG synchronisations, which is only useful
+    /// for old players unaware of ID3v2 tags
+    pub fn set_unsynchronisation(&mut self, unsynchronisation: bool) {
+        self._flags_header.retain(|flag| *flag != Unsynchronisation);
+        if unsynchronisation {
+            self._flags_header.push(Unsynchronisation)
+        }
+    }
+
+    /// Returns the version of the tag
+    pub fn version(&self) -> ID3Version {
+        ID3Version::from_raw_value(self.major_version).unwrap_or_default()
+    }
+
+    /// Change the version the tag will be written with
+    ///
+    /// The frames which have been replaced between ID3v2.3 and ID3v2.4 are converted:
+    /// * TYER, TDAT and TIME to TDRC
+    /// * TORY to TDOR
+    /// * IPLS to TIPL, and TIPL and TMCL to IPLS
+    /// * RVAD to RVA2 and back
+    /// * EQUA to EQU2 and back
+    ///
+    /// The other frames not defined by `version` are dropped.
+    /// The text encodings not allowed in ID3v2.3 are switched to UTF-16
+    pub fn set_version(&mut self, version: ID3Version) {
+        if version as u8 == self.major_version {
+            return;
+        }
+        self.major_version = version as u8;
+        // Converting the frames to their counterparts isn't a tag alteration
+        let altered = self.altered;
+        match version {
+            ID3Version::V2_3 => self.downgrade_frames(),
+            ID3Version::V2_4 => self.upgrade_frames()
+        }
+        self.altered = altered;
+        let major_version = self.major_version;
+        self.frames
+            .iter_mut()
+            .for_each(|frame| frame.adapt_to_version(major_version));
+        self.update_size()
+    }
+}
+
+impl ID3TAG {
+    /// Returns the extended header of the tag
+    pub fn extended_header(&self) -> Option<&ID3ExtendedHeader> {
+        self.extended_header.as_ref()
+    }
+
+    /// Returns the extended header of the tag to modify it
+    pub fn extended_header_mut(&mut self) -> Option<&mut ID3ExtendedHeader> {
+        self.extended_header.as_mut()
+    }
+
+    /// Set or remove the extended header written with the tag
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::{ID3TAG, ID3ExtendedHeader};
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// let mut extended_header = ID3ExtendedHeader::new();
+    /// extended_header.set_crc(true);
+    /// metadata.set_extended_header(Some(extended_header));
+    /// ```
+    pub fn set_extended_header(&mut self, extended_header: Option<ID3ExtendedHeader>) {
+        self._flags_header.retain(|flag| *flag != ExtendedHeader);
+        if extended_header.is_some() {
+            self._flags_header.push(ExtendedHeader)
+        }
+        self.extended_header = extended_header;
+        self.update_size()
+    }
+
+    /// Returns the size from which APIC, GEOB and PRIV frames are compressed when the tag is written
+    pub fn compression_threshold(&self) -> Option<usize> {
+        self.compression_threshold
+    }
+
+    /// Compress the APIC, GEOB and PRIV frames of at least `threshold` bytes with zlib when the tag is written.
+    ///
+    /// `None` disables the compression. Frames read compressed are always written back compressed
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::ID3TAG;
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// metadata.set_compression_threshold(Some(4096));
+    /// ```
+    pub fn set_compression_threshold(&mut self, threshold: Option<usize>) {
+        self.compression_threshold = threshold
+    }
+
+    /// Whether the CRC-32 stored in the extended header matches the frames read from the file
+    ///
+    /// Returns `None` if the tag read had no CRC
+    pub fn is_crc_valid(&self) -> Option<bool> {
+        self.crc_check
+    }
+}
+
+impl ID3TAG {
+    /// Returns the status flags of the frames `frame_id` (ex: "TIT2", "APIC")
+    pub fn frame_status_flags(&self, frame_id: &str) -> Vec<FrameStatusFlags> {
+        let frame_id = match ID3FRAMEID::from_raw_id(frame_id.as_bytes()) {
+            Ok(frame_id) => frame_id,
+            Err(_) => return vec![]
+        };
+        self.frames
+            .iter()
+            .filter(|frame| frame.get_frame_id() == &frame_id)
+            .map(|frame| frame.status_flags())
+            .collect()
+    }
+
+    /// Set the status flags of the frames `frame_id` (ex: "TIT2", "APIC")
+    ///
+    /// Returns `false` if the tag has no such frame
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::{ID3TAG, FrameStatusFlags};
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// let flags = FrameStatusFlags { read_only: true, ..Default::default() };
+    /// if metadata.set_frame_status_flags("TIT2", flags) {
+    ///     metadata.set_title("Ignored");
+    /// }
+    /// ```
+    pub fn set_frame_status_flags(&mut self, frame_id: &str, flags: FrameStatusFlags) -> bool {
+        let frame_id = match ID3FRAMEID::from_raw_id(frame_id.as_bytes()) {
+            Ok(frame_id) => frame_id,
+            Err(_) => return false
+        };
+        let mut found = false;
+        self.frames
+            .iter_mut()
+            .filter(|frame| frame.get_frame_id() == &frame_id)
+            .for_each(|frame| {
+                frame.set_status_flags(flags);
+                found = true
+            });
+        found
+    }
+
+    /// Whether the read only frames can be edited
+    pub fn force_read_only_edits(&self) -> bool {
+        self.force_read_only_edits
+    }
+
+    /// Allow the setters to edit and remove the frames flagged as read only
+    pub fn set_force_read_only_edits(&mut self, force: bool) {
+        self.force_read_only_edits = force
+    }
+
+    /// Notify the tag that the audio data has been modified.
+    ///
+    /// The frames flagged to be discarded on file alteration are removed
+    pub fn file_altered(&mut self) {
+        let count = self.frames.len();
+        self.frames.retain(|frame| !frame.status_flags().discard_on_file_alteration);
+        if self.frames.len() != count {
+            self.recalcule_size()
+        }
+    }
+}
+
+impl ID3TAG {
+    /// Returns the ID3v1 tag found at the end of the file
+    pub fn id3v1(&self) -> Option<&ID3v1TAG> {
+        self.id3v1.as_ref()
+    }
+
+    /// Returns a mutable reference to the ID3v1 tag found at the end of the file
+    pub fn id3v1_mut(&mut self) -> Option<&mut ID3v1TAG> {
+        self.id3v1.as_mut()
+    }
+
+    /// Replace or remove the ID3v1 tag written with [ID3v1Mode::Keep]
+    pub fn set_id3v1(&mut self, id3v1: Option<ID3v1TAG>) {
+        self.id3v1 = id3v1
+    }
+
+    /// Returns how the ID3v1 tag will be written
+    pub fn id3v1_mode(&self) -> ID3v1Mode {
+        self.id3v1_mode
+    }
+
+    /// Set how the ID3v1 tag will be written
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use tag_edit::{ID3TAG, ID3v1Mode};
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// metadata.set_title("A title");
+    /// metadata.set_id3v1_mode(ID3v1Mode::Sync);
+    /// assert_eq!(metadata.synced_id3v1().title(), "A title");
+    /// ```
+    pub fn set_id3v1_mode(&mut self, mode: ID3v1Mode) {
+        self.id3v1_mode = mode
+    }
+
+    /// Build the ID3v1 tag mirroring the ID3v2 frames
+    ///
+    /// The fields of the Enhanced block without ID3v2 counterpart are taken from the current ID3v1 tag
+    pub fn synced_id3v1(&self) -> ID3v1TAG {
+        let clean = |text: String| text.trim_matches(|c| c == '\u{0}' || c == '\u{feff}').to_string();
+        let mut id3v1 = self.id3v1.clone().unwrap_or_default();
+        id3v1.set_title(&self.title().map(clean).unwrap_or_default());
+        id3v1.set_artist(&self.artist().map(clean).unwrap_or_default());
+        id3v1.set_album(&self.album().map(clean).unwrap_or_default());
+        id3v1.set_year(&self.year().map(|year| year.to_string()).unwrap_or_default());
+        id3v1.set_comment(&self.comments().into_iter().next().map(|(_, text)| clean(text)).unwrap_or_default());
+        id3v1.set_track(
+            self.track_position()
+            .map(clean)
+            .and_then(|track| track.split('/').next()?.trim().parse().ok())
+        );
+        id3v1.set_genre(self.genre().and_then(|genre| genre_index(&clean(genre))).unwrap_or(NO_GENRE));
+        id3v1
+    }
+}
+
 impl ID3TAG {
 
     /// Overwrite the tag in the origin file
-    pub fn overwrite_tag(&self) -> Result<(), Error>{
-        self.write_tag(self.file_path.as_str())
+    ///
+    /// If the tag fits in the space it had in the file, only the beginning and the end (ID3v1 tag)
+    /// of the file are rewritten. Otherwise, the whole file is rewritten with a padding following the
+    /// [PaddingPolicy], see [ID3TAG::set_padding_policy]
+    ///
+    /// Returns the strategy used to write the tag
+    pub fn overwrite_tag(&self) -> Result<WriteStrategy, Error>{
+        let tag = self.id3v2_bytes();
+        if let Some(original_size) = self.original_tag_size.filter(|size| *size == tag.len()) {
+            let mut file = OpenOptions::new().read(true).write(true).open(self.file_path.as_str())?;
+            let expected_len = original_size + self.music_data.len() + self.original_id3v1_size;
+            if on_disk_tag_size(&mut file)? == Some(original_size) && file.metadata()?.len() == expected_len as u64 {
+                file.seek(SeekFrom::Start(0))?;
+                file.write_all(&tag)?;
+                let id3v1 = self.id3v1_bytes();
+                let audio_end = original_size + self.music_data.len();
+                file.seek(SeekFrom::Start(audio_end as u64))?;
+                file.write_all(&id3v1)?;
+                file.set_len((audio_end + id3v1.len()) as u64)?;
+                return Ok(WriteStrategy::InPlace)
+            }
+        }
+        self.write_tag(self.file_path.as_str())?;
+        Ok(WriteStrategy::FullRewrite)
+    }
+
+    /// Returns the padding policy used when the tag doesn't fit in the space it had in the file
+    pub fn padding_policy(&self) -> PaddingPolicy {
+        self.padding_policy
+    }
+
+    /// Set the padding policy used when the tag doesn't fit in the space it had in the file
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::{ID3TAG, PaddingPolicy};
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// metadata.set_padding_policy(PaddingPolicy::Aligned4KiB);
+    /// ```
+    pub fn set_padding_policy(&mut self, padding_policy: PaddingPolicy) {
+        self.padding_policy = padding_policy;
+        self.update_size()
     }
 
     /// Write the tag and the audio content at `path`.
@@ -414,6 +1080,25 @@ impl ID3TAG {
     pub fn remove_all_attached_pictures(&mut self) {
         self.remove_frames(&APIC)
     }
+    /// Returns the content of the text frame `frame_id`
+    pub fn get_text_frame(&self, frame_id: ID3TEXTFRAMEID) -> Option<String> {
+        self.get_text_from_text_frame(&TEXTFRAME(frame_id))
+    }
+    /// Add a text frame to the tag.
+    /// Replace the content if the text frame already exists
+    pub fn add_text_frame(&mut self, frame_id: ID3TEXTFRAMEID, content: &str) {
+        self.set_text_frame(TEXTFRAME(frame_id), content.into())
+    }
+    /// Remove the text frame `frame_id`
+    pub fn remove_text_frame(&mut self, frame_id: ID3TEXTFRAMEID) {
+        self.remove_frames(&TEXTFRAME(frame_id))
+    }
+    /// Returns the recording time in the ISO 8601 format (TDRC)
+    ///
+    /// Only ID3v2.4 defines this frame
+    pub fn recording_time(&self) -> Option<String> {
+        self.get_text_from_text_frame(&TEXTFRAME(TDRC))
+    }
     /// Returns the song artist (TPE1)
     ///
     /// # Examples
@@ -835,7 +1520,7 @@ impl ID3TAG {
     pub fn remove_music_len(&mut self) {
         self.remove_frames(&TEXTFRAME(TLEN))
     }
-    /// Returns the track's year (TYER)
+    /// Returns the track's year (TYER or TDRC in ID3v2.4)
     ///
     /// # Example
     /// ```
@@ -846,11 +1531,13 @@ impl ID3TAG {
     ///
     /// ```
     pub fn year(&self) -> Option<i16> {
-        self.get_text_from_text_frame(&TEXTFRAME(TYER))?
+        self.get_text_from_text_frame(&TEXTFRAME(TYER))
+            .or_else(|| self.get_text_from_text_frame(&TEXTFRAME(TDRC)))?
+            .get(0..4)?
             .parse()
             .ok()
     }
-    /// Set the track year (TYER)
+    /// Set the track year (TYER or TDRC in ID3v2.4)
     ///
     /// # Examples
     /// ```
@@ -861,9 +1548,15 @@ impl ID3TAG {
     ///
     /// ```
     pub fn set_year(&mut self, year: i16) {
-        self.set_text_frame(TEXTFRAME(TYER), year.to_string())
+        if self.version() == ID3Version::V2_4 {
+            let recording_time = self.get_text_from_text_frame(&TEXTFRAME(TDRC)).unwrap_or_default();
+            let rest = recording_time.get(4..).unwrap_or_default();
+            self.set_text_frame(TEXTFRAME(TDRC), format!("{:04}{}", year, rest))
+        } else {
+            self.set_text_frame(TEXTFRAME(TYER), year.to_string())
+        }
     }
-    /// Remove the year (TYER)
+    /// Remove the year (TYER and TDRC)
     ///
     /// # Examples
     ///
@@ -877,7 +1570,8 @@ impl ID3TAG {
     ///
     /// ```
     pub fn remove_year(&mut self) {
-        self.remove_frames(&TEXTFRAME(TYER))
+        self.remove_frames(&TEXTFRAME(TYER));
+        self.remove_frames(&TEXTFRAME(TDRC))
     }
     /// Returns the track position in the disc (TRCK)
     ///
@@ -992,6 +1686,71 @@ impl ID3TAG {
     pub fn remove_all_lyrics(&mut self) {
         self.remove_frames(&USLT)
     }
+    /// Returns the synchronised lyrics in the tag
+    pub fn synced_lyrics(&self) -> Vec<&SyncLyricsFrame> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.as_sync_lyrics_frame())
+            .collect()
+    }
+    /// Add synchronised lyrics to the tag
+    ///
+    /// # Errors
+    /// This function will return an `TagError` if :
+    ///
+    /// * the language of `lyrics` is not ascii or length != 3
+    /// * Tuple(`language`, `description`) already exists in the synchronised lyrics frames
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::{ID3TAG, SyncLyricsFrame};
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// let lyrics = SyncLyricsFrame::from_lrc("[00:12.00]First line\n[00:17.20]Second line", "eng", "");
+    /// metadata.add_synced_lyrics(lyrics).unwrap();
+    /// assert_eq!(metadata.synced_lyrics().first().unwrap().lyrics()[1], ("Second line".to_string(), 17200))
+    /// ```
+    pub fn add_synced_lyrics(&mut self, lyrics: SyncLyricsFrame) -> Result<(), TagError> {
+        if lyrics.language().len() != 3 || !lyrics.language().is_ascii() {
+            Err(TagError::LangWrongSize)
+        } else if self.synced_lyrics().iter().any(|f| {
+            f.language() == lyrics.language() && f.description() == lyrics.description()
+        }) {
+            Err(TagError::ReusedLangDescription)
+        } else {
+            let frame = (ID3FRAMEID::SYLT, FrameValue::SLF(lyrics)).into();
+            self.frames.push(frame);
+            self.recalcule_size();
+            Ok(())
+        }
+    }
+    /// Add synchronised lyrics to the tag from a LRC file
+    ///
+    /// See [ID3TAG::add_synced_lyrics] for the errors
+    pub fn add_synced_lyrics_from_lrc_file(
+        &mut self,
+        file_path: &str,
+        lang: &str,
+        description: Option<String>,
+    ) -> Result<(), TagError> {
+        let mut lrc = String::new();
+        File::open(file_path)?.read_to_string(&mut lrc)?;
+        let lyrics = SyncLyricsFrame::from_lrc(&lrc, lang, &description.unwrap_or_default());
+        self.add_synced_lyrics(lyrics)
+    }
+    /// Remove the synchronised lyrics identified by `lang` and `description`
+    ///
+    /// Returns `false` if no such lyrics were removed
+    pub fn remove_synced_lyrics(&mut self, lang: &str, description: &str) -> bool {
+        self.remove_frames_matching(|frame| {
+            frame
+                .as_sync_lyrics_frame()
+                .is_some_and(|f| f.language() == lang && f.description() == description)
+        })
+    }
+    /// Remove all the synchronised lyrics in the tag
+    pub fn remove_all_synced_lyrics(&mut self) {
+        self.remove_frames(&SYLT)
+    }
     /// Returns the comments in the tags
     pub fn comments(&self) -> Vec<(String, String)> {
         self.get_comments()
@@ -1011,3 +1770,1076 @@ impl ID3TAG {
         self.remove_frames(&COMM)
     }
 }
+
+impl ID3TAG {
+    /// Returns the popularimeters (POPM) of the tag
+    pub fn popularimeters(&self) -> Vec<&PopularimeterFrame> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.as_popularimeter_frame())
+            .collect()
+    }
+    /// Returns the rating given by the user identified by `email`, from 1 (worst) to 255 (best)
+    ///
+    /// `None` if the user didn't rate the file
+    pub fn rating(&self, email: &str) -> Option<u8> {
+        self.popularimeters()
+            .into_iter()
+            .find(|f| f.email() == email)
+            .map(|f| f.rating())
+            .filter(|rating| *rating != 0)
+    }
+    /// Set the rating given by the user identified by `email`, from 1 (worst) to 255 (best), 0 for unknown
+    ///
+    /// The play counter of the user is kept. Returns `false` if the popularimeter of the user is read only
+    pub fn set_rating(&mut self, email: &str, rating: u8) -> bool {
+        match self.find_frame_mut(|frame| frame.as_popularimeter_frame().is_some_and(|f| f.email() == email)) {
+            Err(()) => return false,
+            Ok(Some(frame)) => {
+                frame.as_popularimeter_frame_mut().unwrap().set_rating(rating);
+                frame.mark_rewritten()
+            }
+            Ok(None) => {
+                let value = PopularimeterFrame::new(email, rating, None);
+                self.frames.push((POPM, FrameValue::PF(value)).into())
+            }
+        }
+        self.recalcule_size();
+        true
+    }
+    /// Returns the rating given by the user identified by `email` on the star scale of a player
+    pub fn stars(&self, email: &str, scale: RatingScale) -> Option<f32> {
+        scale.stars(self.rating(email)?)
+    }
+    /// Set the rating given by the user identified by `email` on the star scale of a player
+    ///
+    /// See [ID3TAG::set_rating]
+    pub fn set_stars(&mut self, email: &str, scale: RatingScale, stars: f32) -> bool {
+        self.set_rating(email, scale.rating(stars))
+    }
+    /// Remove the popularimeter of the user identified by `email`
+    pub fn remove_rating(&mut self, email: &str) -> bool {
+        self.remove_frames_matching(|frame| {
+            frame.as_popularimeter_frame().is_some_and(|f| f.email() == email)
+        })
+    }
+    /// Returns the number of times the file has been played (PCNT)
+    pub fn play_count(&self) -> Option<u64> {
+        self.frames
+            .iter()
+            .find_map(|frame| frame.as_play_counter_frame())
+            .map(|f| f.counter())
+    }
+    /// Set the number of times the file has been played
+    ///
+    /// Returns `false` if the play counter is read only
+    pub fn set_play_count(&mut self, count: u64) -> bool {
+        match self.find_frame_mut(|frame| frame.as_play_counter_frame().is_some()) {
+            Err(()) => return false,
+            Ok(Some(frame)) => {
+                frame.as_play_counter_frame_mut().unwrap().set_counter(count);
+                frame.mark_rewritten()
+            }
+            Ok(None) => self.frames.push((PCNT, FrameValue::PCF(PlayCounterFrame::new(count))).into()),
+        }
+        self.recalcule_size();
+        true
+    }
+    /// Increment the play counter, and the one of the user identified by `email` if any
+    ///
+    /// Returns `false` if one of the counters is read only
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::ID3TAG;
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// let count = metadata.play_count().unwrap_or(0);
+    /// metadata.increment_play_count(None);
+    /// assert_eq!(metadata.play_count(), Some(count + 1))
+    /// ```
+    pub fn increment_play_count(&mut self, email: Option<&str>) -> bool {
+        let is_user_popularimeter = |frame: &ID3FRAME| {
+            frame.as_popularimeter_frame().is_some_and(|f| Some(f.email()) == email)
+        };
+        // Both counters have to be writable before touching either one
+        if email.is_some() && self.find_frame_mut(is_user_popularimeter).is_err() {
+            return false;
+        }
+        let count = self.play_count().unwrap_or(0).saturating_add(1);
+        if !self.set_play_count(count) {
+            return false;
+        }
+        if let Some(email) = email {
+            match self.find_frame_mut(is_user_popularimeter) {
+                Ok(Some(frame)) => {
+                    let popularimeter = frame.as_popularimeter_frame_mut().unwrap();
+                    popularimeter.set_counter(Some(popularimeter.counter().unwrap_or(0).saturating_add(1)));
+                    frame.mark_rewritten()
+                }
+                _ => {
+                    let value = PopularimeterFrame::new(email, 0, Some(1));
+                    self.frames.push((POPM, FrameValue::PF(value)).into())
+                }
+            }
+            self.recalcule_size();
+        }
+        true
+    }
+}
+
+impl ID3TAG {
+    /// Returns the event timing codes (ETCO) of the tag
+    pub fn event_timing_codes(&self) -> Option<&EventTimingFrame> {
+        self.frames.iter().find_map(|frame| frame.as_event_timing_frame())
+    }
+    /// Returns the timestamp of the first occurrence of `event` in the event timing codes
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::{EventType, ID3TAG, TimeStampFormat};
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// metadata.set_event_timing_codes(TimeStampFormat::Milliseconds, vec![
+    ///     (EventType::OutroStart, 215_000),
+    ///     (EventType::IntroEnd, 12_500),
+    /// ]);
+    /// assert_eq!(metadata.event_timestamp(&EventType::OutroStart), Some(215_000))
+    /// ```
+    pub fn event_timestamp(&self, event: &EventType) -> Option<u32> {
+        self.event_timing_codes()?.timestamp(event)
+    }
+    /// Replace the event timing codes, the events are sorted by timestamp
+    ///
+    /// Returns `false` if the event timing codes are read only
+    pub fn set_event_timing_codes(
+        &mut self,
+        time_stamp_format: TimeStampFormat,
+        events: Vec<(EventType, u32)>,
+    ) -> bool {
+        let value = FrameValue::ETF(EventTimingFrame::new(time_stamp_format, events));
+        match self.find_frame_mut(|frame| frame.get_frame_id() == &ETCO) {
+            Err(()) => return false,
+            Ok(Some(frame)) => {
+                *frame.get_frame_value_mut() = value;
+                frame.mark_rewritten()
+            }
+            Ok(None) => self.frames.push((ETCO, value).into()),
+        }
+        self.recalcule_size();
+        true
+    }
+    /// Remove the event timing codes
+    pub fn remove_event_timing_codes(&mut self) {
+        self.remove_frames(&ETCO)
+    }
+}
+
+impl ID3TAG {
+    /// Returns the tempo map (SYTC) of the tag
+    pub fn tempo_map(&self) -> Option<&SyncTempoCodeFrame> {
+        self.frames.iter().find_map(|frame| frame.as_sync_tempo_code_frame())
+    }
+    /// Returns the tempo in BPM at the time `timestamp`, expressed in the unit of the tempo map
+    ///
+    /// `None` if there is no tempo map, before its first tempo and during the beat free periods
+    pub fn tempo_at(&self, timestamp: u32) -> Option<u16> {
+        self.tempo_map()?.tempo_at(timestamp)
+    }
+    /// Replace the tempo map, the tempos being sorted by timestamp
+    ///
+    /// The BPM (TBPM) is set to the average tempo of the map. When the timestamps are in milliseconds,
+    /// the last tempo lasts until the end of the music if its length (TLEN) is known.
+    ///
+    /// Returns `false` if the tempo map is read only
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::{ID3TAG, TimeStampFormat};
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// metadata.set_tempo_map(TimeStampFormat::Milliseconds, vec![(120, 0), (140, 60_000), (120, 120_000)]);
+    /// assert_eq!(metadata.tempo_at(90_000), Some(140));
+    /// ```
+    pub fn set_tempo_map(&mut self, time_stamp_format: TimeStampFormat, tempos: Vec<(u16, u32)>) -> bool {
+        let tempo_map = SyncTempoCodeFrame::new(time_stamp_format, tempos);
+        let end = match time_stamp_format {
+            TimeStampFormat::Milliseconds => self.music_len().map(|len| len as u32),
+            TimeStampFormat::MPEGFrames => None,
+        };
+        let average = tempo_map.average_tempo(end);
+        match self.find_frame_mut(|frame| frame.get_frame_id() == &SYTC) {
+            Err(()) => return false,
+            Ok(Some(frame)) => {
+                *frame.get_frame_value_mut() = FrameValue::SYCF(tempo_map);
+                frame.mark_rewritten()
+            }
+            Ok(None) => self.frames.push((SYTC, FrameValue::SYCF(tempo_map)).into()),
+        }
+        match average {
+            Some(average) => self.set_bpm(average.round() as u16),
+            None => self.remove_bpm(),
+        }
+        self.recalcule_size();
+        true
+    }
+    /// Remove the tempo map
+    pub fn remove_tempo_map(&mut self) {
+        self.remove_frames(&SYTC)
+    }
+}
+
+impl ID3TAG {
+    /// Returns the MPEG location lookup table (MLLT) of the tag
+    pub fn location_lookup_table(&self) -> Option<&LocationLookupTableFrame> {
+        self.frames.iter().find_map(|frame| frame.as_location_lookup_table_frame())
+    }
+    /// Build the MPEG location lookup table by scanning the MPEG frames following the tag,
+    /// with a reference every `frames_between_reference` frames
+    ///
+    /// Returns `false` if the table can't be built (no MPEG frames, references too far apart)
+    /// or if the existing table is read only
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::ID3TAG;
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// assert!(metadata.generate_location_lookup_table(40));
+    /// let (_offset, time) = metadata.location_lookup_table().unwrap().seek(60_000);
+    /// assert!(time <= 60_000);
+    /// ```
+    pub fn generate_location_lookup_table(&mut self, frames_between_reference: u16) -> bool {
+        let frames = mpeg_frames(&self.music_data);
+        let first_offset = match frames.first() {
+            Some((offset, _)) => *offset,
+            None => return false,
+        };
+        let frames = frames
+            .iter()
+            .map(|(offset, header)| (offset - first_offset, header.duration()))
+            .collect::<Vec<(usize, f64)>>();
+        match LocationLookupTableFrame::from_frames(&frames, frames_between_reference) {
+            Some(table) => self.set_location_lookup_table(table),
+            None => false,
+        }
+    }
+    /// Replace the MPEG location lookup table
+    ///
+    /// Returns `false` if the existing table is read only
+    pub fn set_location_lookup_table(&mut self, table: LocationLookupTableFrame) -> bool {
+        match self.find_frame_mut(|frame| frame.get_frame_id() == &MLLT) {
+            Err(()) => return false,
+            Ok(Some(frame)) => {
+                *frame.get_frame_value_mut() = FrameValue::LLTF(table);
+                frame.mark_rewritten()
+            }
+            Ok(None) => self.frames.push((MLLT, FrameValue::LLTF(table)).into()),
+        }
+        self.recalcule_size();
+        true
+    }
+    /// Remove the MPEG location lookup table
+    pub fn remove_location_lookup_table(&mut self) {
+        self.remove_frames(&MLLT)
+    }
+}
+
+impl ID3TAG {
+    /// Returns the relative volume adjustments of the tag (RVAD or RVA2)
+    pub fn relative_volume_adjustments(&self) -> Vec<&RelativeVolumeAdjustementFrame> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.as_relative_volume_frame())
+            .collect()
+    }
+    /// Returns the relative volume adjustment identified by `identification` (RVA2),
+    /// the RVAD frame in an ID3v2.3 tag
+    pub fn relative_volume_adjustment(&self, identification: &str) -> Option<&RelativeVolumeAdjustementFrame> {
+        self.relative_volume_adjustments()
+            .into_iter()
+            .find(|f| f.identification().is_none_or(|id| id == identification))
+    }
+    /// Set the relative volume adjustment identified by `identification`
+    ///
+    /// An ID3v2.4 tag stores it in a RVA2 frame, an ID3v2.3 tag in its single RVAD frame
+    /// whatever the identification.
+    ///
+    /// Returns `false` if the frame is read only
+    pub fn set_relative_volume_adjustment(&mut self, identification: &str, channels: Vec<ChannelAdjustment>) -> bool {
+        let (frame_id, volume) = if self.major_version == 4 {
+            (RVA2, RelativeVolumeAdjustementFrame::new(identification, channels))
+        } else {
+            (RVAD, RelativeVolumeAdjustementFrame::new_v23(channels))
+        };
+        match self.find_frame_mut(|frame| {
+            frame.get_frame_id() == &frame_id
+                && frame.as_relative_volume_frame().is_some_and(|f| f.identification().is_none_or(|id| id == identification))
+        }) {
+            Err(()) => return false,
+            Ok(Some(frame)) => {
+                *frame.get_frame_value_mut() = FrameValue::RVAF(volume);
+                frame.mark_rewritten()
+            }
+            Ok(None) => self.frames.push((frame_id, FrameValue::RVAF(volume)).into()),
+        }
+        self.recalcule_size();
+        true
+    }
+    /// Remove the relative volume adjustment identified by `identification` (RVA2),
+    /// the RVAD frame in an ID3v2.3 tag
+    pub fn remove_relative_volume_adjustment(&mut self, identification: &str) -> bool {
+        self.remove_frames_matching(|frame| {
+            frame.as_relative_volume_frame().is_some_and(|f| f.identification().is_none_or(|id| id == identification))
+        })
+    }
+    /// Remove all the relative volume adjustments
+    pub fn remove_all_relative_volume_adjustments(&mut self) {
+        self.remove_frames(&RVAD);
+        self.remove_frames(&RVA2)
+    }
+    /// Returns the ReplayGain values read from the TXXX frames (`REPLAYGAIN_TRACK_GAIN`, ...)
+    pub fn replay_gain_from_txxx(&self, scope: ReplayGainScope) -> Option<ReplayGain> {
+        let gain = ReplayGain::parse_gain(&self.get_user_text(scope.gain_description())?)?;
+        let peak = self
+            .get_user_text(scope.peak_description())
+            .and_then(|peak| ReplayGain::parse_peak(&peak));
+        Some(ReplayGain { gain, peak })
+    }
+    /// Returns the ReplayGain values read from the relative volume adjustment
+    /// (the RVA2 frame `"track"` or `"album"`, the RVAD frame for the track)
+    pub fn replay_gain_from_relative_volume(&self, scope: ReplayGainScope) -> Option<ReplayGain> {
+        let (gain, peak) = self
+            .relative_volume_adjustments()
+            .into_iter()
+            .find(|f| match f.identification() {
+                Some(identification) => identification.eq_ignore_ascii_case(scope.identification()),
+                None => scope == ReplayGainScope::Track,
+            })?
+            .master()?;
+        Some(ReplayGain { gain, peak })
+    }
+    /// Returns the ReplayGain values, read from the TXXX frames or else from the relative volume adjustment
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::{ID3TAG, ReplayGain, ReplayGainScope};
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// metadata.set_replay_gain(ReplayGainScope::Track, ReplayGain { gain: -6.5, peak: Some(0.98) });
+    /// assert_eq!(metadata.replay_gain_from_relative_volume(ReplayGainScope::Track).unwrap().gain, -6.5);
+    /// ```
+    pub fn replay_gain(&self, scope: ReplayGainScope) -> Option<ReplayGain> {
+        self.replay_gain_from_txxx(scope)
+            .or_else(|| self.replay_gain_from_relative_volume(scope))
+    }
+    /// Write the ReplayGain values both in the TXXX frames and in the relative volume adjustment
+    ///
+    /// An ID3v2.3 tag only stores the track values in its RVAD frame.
+    /// Returns `false` if one of the frames is read only
+    pub fn set_replay_gain(&mut self, scope: ReplayGainScope, replay_gain: ReplayGain) -> bool {
+        let mut written = self.set_user_text(scope.gain_description(), &replay_gain.gain_text());
+        written &= match replay_gain.peak {
+            Some(peak) => self.set_user_text(scope.peak_description(), &ReplayGain::peak_text(peak)),
+            None => {
+                self.remove_user_text(scope.peak_description());
+                true
+            }
+        };
+        if self.major_version == 4 || scope == ReplayGainScope::Track {
+            let channel = ChannelAdjustment {
+                channel: ChannelType::MasterVolume,
+                adjustment: replay_gain.gain,
+                peak: replay_gain.peak,
+            };
+            written &= self.set_relative_volume_adjustment(scope.identification(), vec![channel]);
+        }
+        written
+    }
+    /// Remove the ReplayGain values from the TXXX frames and the relative volume adjustment
+    pub fn remove_replay_gain(&mut self, scope: ReplayGainScope) {
+        self.remove_user_text(scope.gain_description());
+        self.remove_user_text(scope.peak_description());
+        if self.major_version == 4 || scope == ReplayGainScope::Track {
+            self.remove_relative_volume_adjustment(scope.identification());
+        }
+    }
+    /// Write the ReplayGain values of the TXXX frames in the relative volume adjustment
+    ///
+    /// Returns `false` if there is nothing to convert or if a frame is read only
+    pub fn replay_gain_to_relative_volume(&mut self) -> bool {
+        let mut converted = false;
+        for scope in [ReplayGainScope::Track, ReplayGainScope::Album] {
+            if let Some(replay_gain) = self.replay_gain_from_txxx(scope) {
+                converted = self.set_replay_gain(scope, replay_gain);
+            }
+        }
+        converted
+    }
+    /// Write the relative volume adjustment in the ReplayGain TXXX frames
+    ///
+    /// Returns `false` if there is nothing to convert or if a frame is read only
+    pub fn relative_volume_to_replay_gain(&mut self) -> bool {
+        let mut converted = false;
+        for scope in [ReplayGainScope::Track, ReplayGainScope::Album] {
+            if let Some(replay_gain) = self.replay_gain_from_relative_volume(scope) {
+                converted = self.set_replay_gain(scope, replay_gain);
+            }
+        }
+        converted
+    }
+}
+
+impl ID3TAG {
+    /// Returns the equalisation of an ID3v2.3 tag (EQUA)
+    pub fn equalisation(&self) -> Option<&EqualiisationFrame> {
+        self.frames.iter().find_map(|frame| frame.as_equalisation_frame())
+    }
+    /// Replace the equalisation of an ID3v2.3 tag (EQUA)
+    ///
+    /// Returns `false` if the equalisation is read only
+    pub fn set_equalisation(&mut self, equalisation: EqualiisationFrame) -> bool {
+        self.replace_frame(EQUA, FrameValue::EF(equalisation), |frame| frame.get_frame_id() == &EQUA)
+    }
+    /// Remove the equalisation of an ID3v2.3 tag (EQUA)
+    pub fn remove_equalisation(&mut self) {
+        self.remove_frames(&EQUA)
+    }
+    /// Returns the equalisations of an ID3v2.4 tag (EQU2)
+    pub fn equalisations2(&self) -> Vec<&Equalisation2Frame> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.as_equalisation2_frame())
+            .collect()
+    }
+    /// Add an equalisation to an ID3v2.4 tag (EQU2), replacing the one with the same identification
+    ///
+    /// Returns `false` if the replaced equalisation is read only
+    pub fn set_equalisation2(&mut self, equalisation: Equalisation2Frame) -> bool {
+        let identification = equalisation.identification().to_string();
+        self.replace_frame(EQU2, FrameValue::E2F(equalisation), |frame| {
+            frame.as_equalisation2_frame().is_some_and(|f| f.identification() == identification)
+        })
+    }
+    /// Remove the equalisation identified by `identification` of an ID3v2.4 tag (EQU2)
+    pub fn remove_equalisation2(&mut self, identification: &str) -> bool {
+        self.remove_frames_matching(|frame| {
+            frame.as_equalisation2_frame().is_some_and(|f| f.identification() == identification)
+        })
+    }
+    /// Returns the reverb settings (RVRB)
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::{ID3TAG, ReverbFrame};
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// let mut reverb = metadata.reverb().cloned().unwrap_or_default();
+    /// reverb.reverb_left = 120;
+    /// metadata.set_reverb(reverb);
+    /// assert_eq!(metadata.reverb().unwrap().reverb_left, 120);
+    /// ```
+    pub fn reverb(&self) -> Option<&ReverbFrame> {
+        self.frames.iter().find_map(|frame| frame.as_reverb_frame())
+    }
+    /// Replace the reverb settings (RVRB)
+    ///
+    /// Returns `false` if the reverb settings are read only
+    pub fn set_reverb(&mut self, reverb: ReverbFrame) -> bool {
+        self.replace_frame(RVRB, FrameValue::RF(reverb), |frame| frame.get_frame_id() == &RVRB)
+    }
+    /// Remove the reverb settings (RVRB)
+    pub fn remove_reverb(&mut self) {
+        self.remove_frames(&RVRB)
+    }
+}
+
+impl ID3TAG {
+    /// Returns the general encapsulated objects (GEOB)
+    pub fn encapsulated_objects(&self) -> Vec<&GeneralEncapsulatedObjectFrame> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.as_encapsulated_object_frame())
+            .collect()
+    }
+    /// Returns the encapsulated object whose content description is `description`
+    pub fn encapsulated_object(&self, description: &str) -> Option<&GeneralEncapsulatedObjectFrame> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.as_encapsulated_object_frame())
+            .find(|object| object.description() == description)
+    }
+    /// Add an encapsulated object, replacing the one with the same content description
+    ///
+    /// Returns `false` if the replaced object is read only
+    pub fn set_encapsulated_object(&mut self, object: GeneralEncapsulatedObjectFrame) -> bool {
+        let description = object.description().to_string();
+        self.replace_frame(GEOB, FrameValue::GEOF(object), |frame| {
+            frame.as_encapsulated_object_frame().is_some_and(|f| f.description() == description)
+        })
+    }
+    /// Add the file `file_path` as an encapsulated object identified by `description`,
+    /// replacing the one with the same content description
+    ///
+    /// Returns `Ok(false)` if the replaced object is read only
+    pub fn add_encapsulated_object_from_file(
+        &mut self,
+        file_path: &str,
+        mime_type: &str,
+        description: &str,
+    ) -> Result<bool, Error> {
+        let mut data = vec![];
+        File::open(file_path)?.read_to_end(&mut data)?;
+        let filename = std::path::Path::new(file_path)
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let object = GeneralEncapsulatedObjectFrame::new(mime_type, &filename, description, data);
+        Ok(self.set_encapsulated_object(object))
+    }
+    /// Write the content of the encapsulated object identified by `description` at `path`.
+    /// The file will be created if doesn't exist or will be truncated if exists
+    ///
+    /// Returns `Ok(false)` if there is no such object
+    pub fn extract_encapsulated_object(&self, description: &str, path: &str) -> Result<bool, Error> {
+        match self.encapsulated_object(description) {
+            None => Ok(false),
+            Some(object) => {
+                File::create(path)?.write_all(object.data())?;
+                Ok(true)
+            }
+        }
+    }
+    /// Remove the encapsulated object identified by `description`
+    ///
+    /// Returns `false` if no such object was removed
+    pub fn remove_encapsulated_object(&mut self, description: &str) -> bool {
+        self.remove_frames_matching(|frame| {
+            frame.as_encapsulated_object_frame().is_some_and(|f| f.description() == description)
+        })
+    }
+    /// Remove all the encapsulated objects in the tag
+    pub fn remove_all_encapsulated_objects(&mut self) {
+        self.remove_frames(&GEOB)
+    }
+}
+
+impl ID3TAG {
+    fn serato_object<T: SeratoObject>(&self) -> Option<T> {
+        T::from_bytes(self.encapsulated_object(T::DESCRIPTION)?.data())
+    }
+    fn set_serato_object<T: SeratoObject>(&mut self, object: &T) -> bool {
+        let object = GeneralEncapsulatedObjectFrame::new("application/octet-stream", "", T::DESCRIPTION, object.to_bytes());
+        self.set_encapsulated_object(object)
+    }
+    /// Returns the cue points, loops and colors set by Serato
+    ///
+    /// `None` if there is no "Serato Markers2" object or if it's malformed
+    pub fn serato_markers(&self) -> Option<SeratoMarkers2> {
+        self.serato_object()
+    }
+    /// Replace the "Serato Markers2" object
+    ///
+    /// Returns `false` if the object is read only
+    pub fn set_serato_markers(&mut self, markers: &SeratoMarkers2) -> bool {
+        self.set_serato_object(markers)
+    }
+    /// Returns the beat grid set by Serato
+    ///
+    /// `None` if there is no "Serato BeatGrid" object or if it's malformed
+    pub fn serato_beatgrid(&self) -> Option<SeratoBeatGrid> {
+        self.serato_object()
+    }
+    /// Replace the "Serato BeatGrid" object
+    ///
+    /// Returns `false` if the object is read only
+    pub fn set_serato_beatgrid(&mut self, beatgrid: &SeratoBeatGrid) -> bool {
+        self.set_serato_object(beatgrid)
+    }
+    /// Returns the BPM and the gains computed by Serato
+    ///
+    /// `None` if there is no "Serato Autotags" object or if it's malformed
+    pub fn serato_autotags(&self) -> Option<SeratoAutotags> {
+        self.serato_object()
+    }
+    /// Replace the "Serato Autotags" object
+    ///
+    /// Returns `false` if the object is read only
+    pub fn set_serato_autotags(&mut self, autotags: &SeratoAutotags) -> bool {
+        self.set_serato_object(autotags)
+    }
+    /// Returns the waveform overview computed by Serato
+    ///
+    /// `None` if there is no "Serato Overview" object or if it's malformed
+    pub fn serato_overview(&self) -> Option<SeratoOverview> {
+        self.serato_object()
+    }
+    /// Replace the "Serato Overview" object
+    ///
+    /// Returns `false` if the object is read only
+    pub fn set_serato_overview(&mut self, overview: &SeratoOverview) -> bool {
+        self.set_serato_object(overview)
+    }
+}
+
+impl ID3TAG {
+    /// Returns the private frames (PRIV)
+    pub fn private_frames(&self) -> Vec<&PrivateFrame> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.as_private_frame())
+            .collect()
+    }
+    /// Returns the data of the private frames owned by `owner_id`
+    pub fn private_data(&self, owner_id: &str) -> Vec<&Vec<u8>> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.as_private_frame())
+            .filter(|frame| frame.owner_id() == owner_id)
+            .map(|frame| frame.data())
+            .collect()
+    }
+    /// Add a private frame
+    ///
+    /// Returns `false` if a private frame with the same owner and data already exists
+    pub fn add_private_frame(&mut self, private_frame: PrivateFrame) -> bool {
+        if self.private_frames().contains(&&private_frame) {
+            return false;
+        }
+        self.frames.push((PRIV, FrameValue::PrivF(private_frame)).into());
+        self.recalcule_size();
+        true
+    }
+    /// Replace the data of the first private frame owned by `owner_id`, or add the frame if there is none
+    ///
+    /// Returns `false` if the private frame is read only
+    pub fn set_private_data(&mut self, owner_id: &str, data: Vec<u8>) -> bool {
+        self.replace_frame(PRIV, FrameValue::PrivF(PrivateFrame::new(owner_id, data)), |frame| {
+            frame.as_private_frame().is_some_and(|f| f.owner_id() == owner_id)
+        })
+    }
+    /// Remove the private frames owned by `owner_id`
+    ///
+    /// Returns `false` if no frame was removed
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::ID3TAG;
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// metadata.remove_private_frames("WM/MediaClassPrimaryID");
+    /// assert!(metadata.private_data("WM/MediaClassPrimaryID").is_empty())
+    /// ```
+    pub fn remove_private_frames(&mut self, owner_id: &str) -> bool {
+        self.remove_frames_matching(|frame| {
+            frame.as_private_frame().is_some_and(|f| f.owner_id() == owner_id)
+        })
+    }
+    /// Remove all the private frames in the tag
+    pub fn remove_all_private_frames(&mut self) {
+        self.remove_frames(&PRIV)
+    }
+}
+
+/// Owner of the UFID frame storing the MusicBrainz recording identifier
+const MUSICBRAINZ_OWNER: &str = "http://musicbrainz.org";
+
+impl ID3TAG {
+    /// Returns the unique file identifiers (UFID)
+    pub fn unique_ids(&self) -> Vec<&UniqueFileIdentifierFrame> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.as_unique_file_identifier_frame())
+            .collect()
+    }
+    /// Returns the unique file identifier given by `owner_id`
+    pub fn unique_id(&self, owner_id: &str) -> Option<&Vec<u8>> {
+        self.unique_ids()
+            .into_iter()
+            .find(|frame| frame.owner_id() == owner_id)
+            .map(|frame| frame.id())
+    }
+    /// Set the unique file identifier given by `owner_id`, replacing the previous one of this owner
+    ///
+    /// Returns `false` if `owner_id` is empty, if `id` is longer than 64 bytes
+    /// or if the previous identifier is read only
+    pub fn set_unique_id(&mut self, owner_id: &str, id: &[u8]) -> bool {
+        let unique_id = match UniqueFileIdentifierFrame::new(owner_id, id) {
+            Some(unique_id) => unique_id,
+            None => return false,
+        };
+        self.replace_frame(UFID, FrameValue::UFIF(unique_id), |frame| {
+            frame.as_unique_file_identifier_frame().is_some_and(|f| f.owner_id() == owner_id)
+        })
+    }
+    /// Remove the unique file identifier given by `owner_id`
+    ///
+    /// Returns `false` if no identifier was removed
+    pub fn remove_unique_id(&mut self, owner_id: &str) -> bool {
+        self.remove_frames_matching(|frame| {
+            frame.as_unique_file_identifier_frame().is_some_and(|f| f.owner_id() == owner_id)
+        })
+    }
+    /// Returns the MusicBrainz recording identifier (UUID) stored in the UFID frame of `http://musicbrainz.org`
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::ID3TAG;
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// metadata.set_musicbrainz_recording_id("b1a9c0e9-d987-4042-ae91-78d6a3267d69");
+    /// assert_eq!(metadata.musicbrainz_recording_id().unwrap(), "b1a9c0e9-d987-4042-ae91-78d6a3267d69")
+    /// ```
+    pub fn musicbrainz_recording_id(&self) -> Option<String> {
+        let id = self.unique_id(MUSICBRAINZ_OWNER)?;
+        let id = String::from_utf8(id.clone()).ok()?;
+        Some(id.trim_end_matches('\u{0}').to_string())
+    }
+    /// Set the MusicBrainz recording identifier (UUID)
+    ///
+    /// Returns `false` if the identifier is longer than 64 bytes or the previous one is read only
+    pub fn set_musicbrainz_recording_id(&mut self, recording_id: &str) -> bool {
+        self.set_unique_id(MUSICBRAINZ_OWNER, recording_id.as_bytes())
+    }
+}
+
+impl ID3TAG {
+    /// Returns the URLs of the frame `frame_id`
+    pub fn urls(&self, frame_id: ID3URLFRAMEID) -> Vec<&str> {
+        let frame_id = ID3FRAMEID::from(frame_id);
+        self.frames
+            .iter()
+            .filter(|frame| frame.get_frame_id() == &frame_id)
+            .filter_map(|frame| frame.as_url_frame())
+            .map(|frame| frame.url())
+            .collect()
+    }
+    /// Returns the URL of the frame `frame_id`, the first one if there are several
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::{ID3TAG, ID3URLFRAMEID};
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// metadata.set_url(ID3URLFRAMEID::WPUB, "https://label.example.com");
+    /// assert_eq!(metadata.url(ID3URLFRAMEID::WPUB).unwrap(), "https://label.example.com")
+    /// ```
+    pub fn url(&self, frame_id: ID3URLFRAMEID) -> Option<&str> {
+        self.urls(frame_id).into_iter().next()
+    }
+    /// Replace the URL of the frame `frame_id`, the first one if there are several, or add the frame
+    ///
+    /// Returns `false` if the frame is read only
+    pub fn set_url(&mut self, frame_id: ID3URLFRAMEID, url: &str) -> bool {
+        let frame_id = ID3FRAMEID::from(frame_id);
+        self.replace_frame(frame_id, FrameValue::UF(UrlFrame::new(url)), |frame| frame.get_frame_id() == &frame_id)
+    }
+    /// Add a URL to the frames `frame_id` which can be repeated (WCOM and WOAR),
+    /// the URL of the other frames is replaced as with [ID3TAG::set_url]
+    ///
+    /// Returns `false` if the URL is already in the tag or if the replaced frame is read only
+    pub fn add_url(&mut self, frame_id: ID3URLFRAMEID, url: &str) -> bool {
+        if !frame_id.allows_multiple() {
+            return self.set_url(frame_id, url);
+        }
+        if self.urls(frame_id).contains(&url) {
+            return false;
+        }
+        self.frames.push((frame_id.into(), FrameValue::UF(UrlFrame::new(url))).into());
+        self.recalcule_size();
+        true
+    }
+    /// Remove the frame `frame_id` with the URL `url`
+    ///
+    /// Returns `false` if no frame was removed
+    pub fn remove_url(&mut self, frame_id: ID3URLFRAMEID, url: &str) -> bool {
+        let frame_id = ID3FRAMEID::from(frame_id);
+        self.remove_frames_matching(|frame| {
+            frame.get_frame_id() == &frame_id && frame.as_url_frame().is_some_and(|f| f.url() == url)
+        })
+    }
+    /// Remove all the frames `frame_id`
+    pub fn remove_urls(&mut self, frame_id: ID3URLFRAMEID) {
+        self.remove_frames(&frame_id.into())
+    }
+    /// Returns the user defined URL links (WXXX)
+    pub fn user_urls(&self) -> Vec<&UserUrlFrame> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.as_user_url_frame())
+            .collect()
+    }
+    /// Returns the user defined URL described by `description`
+    pub fn user_url(&self, description: &str) -> Option<&str> {
+        self.user_urls()
+            .into_iter()
+            .find(|frame| frame.description() == description)
+            .map(|frame| frame.url())
+    }
+    /// Set the user defined URL described by `description`, replacing the previous one with this description
+    ///
+    /// Returns `false` if the previous URL is read only
+    pub fn set_user_url(&mut self, description: &str, url: &str) -> bool {
+        self.replace_frame(WXXX, FrameValue::UUF(UserUrlFrame::new(description, url)), |frame| {
+            frame.as_user_url_frame().is_some_and(|f| f.description() == description)
+        })
+    }
+    /// Remove the user defined URL described by `description`
+    ///
+    /// Returns `false` if no URL was removed
+    pub fn remove_user_url(&mut self, description: &str) -> bool {
+        self.remove_frames_matching(|frame| {
+            frame.as_user_url_frame().is_some_and(|f| f.description() == description)
+        })
+    }
+}
+
+/// Compare two descriptions of user defined frames case insensitively
+fn same_description(description: &str, other: &str) -> bool {
+    description == other || description.to_lowercase() == other.to_lowercase()
+}
+
+impl ID3TAG {
+    /// Returns the (role, person) pairs of the people list `frame_id` (IPLS, TIPL or TMCL)
+    fn people_list(&self, frame_id: &ID3FRAMEID) -> Vec<(String, String)> {
+        match frame_id {
+            IPLS => self
+                .frames
+                .iter()
+                .find_map(|frame| frame.as_involved_people_frame())
+                .map(|frame| frame.people().clone())
+                .unwrap_or_default(),
+            _ => {
+                let text = self.get_text_from_text_frame(frame_id).unwrap_or_default();
+                let text = text.trim_end_matches('\u{0}');
+                if text.is_empty() {
+                    return vec![];
+                }
+                let strings = text
+                    .split('\u{0}')
+                    .map(|string| string.trim_start_matches('\u{feff}').to_string())
+                    .collect();
+                people_from_strings(strings)
+            }
+        }
+    }
+    /// Replace the people list `frame_id` (IPLS, TIPL or TMCL), an empty list removes the frame
+    ///
+    /// Returns `false` if the frame is read only
+    fn set_people_list(&mut self, frame_id: ID3FRAMEID, people: Vec<(String, String)>) -> bool {
+        if people.is_empty() {
+            self.remove_frames(&frame_id);
+            return !self.frames.iter().any(|frame| frame.get_frame_id() == &frame_id);
+        }
+        let value = match frame_id {
+            IPLS => FrameValue::IPF(InvolvedPeopleFrame::new(self.major_version, people)),
+            _ => {
+                let strings = people.into_iter().flat_map(|(role, person)| vec![role, person]);
+                let text = strings.collect::<Vec<String>>().join("\u{0}");
+                FrameValue::TF(TextFrame::new(self.major_version, text))
+            }
+        };
+        self.replace_frame(frame_id, value, |frame| frame.get_frame_id() == &frame_id)
+    }
+    /// The frame of the involved people, the musicians sharing it in ID3v2.3
+    fn involved_people_frame_id(&self, musicians: bool) -> ID3FRAMEID {
+        match (self.major_version, musicians) {
+            (3, _) => IPLS,
+            (_, false) => TEXTFRAME(TIPL),
+            (_, true) => TEXTFRAME(TMCL),
+        }
+    }
+    fn add_people(&mut self, musicians: bool, role: &str, person: &str) -> bool {
+        let frame_id = self.involved_people_frame_id(musicians);
+        let mut people = self.people_list(&frame_id);
+        if people.iter().any(|(r, p)| r == role && p == person) {
+            return false;
+        }
+        people.push((role.into(), person.into()));
+        self.set_people_list(frame_id, people)
+    }
+    fn remove_people(&mut self, musicians: bool, role: &str, person: Option<&str>) -> bool {
+        let frame_id = self.involved_people_frame_id(musicians);
+        let mut people = self.people_list(&frame_id);
+        let count = people.len();
+        people.retain(|(r, p)| r != role || person.is_some_and(|person| person != p));
+        people.len() != count && self.set_people_list(frame_id, people)
+    }
+
+    /// Returns the involved people as (role, person) pairs, from the IPLS frame in ID3v2.3
+    /// and the TIPL frame in ID3v2.4
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::ID3TAG;
+    /// let mut metadata = ID3TAG::from_path("file_test/mp3/02 VANISHING POINT.mp3").unwrap();
+    /// metadata.add_involved_person("producer", "Rick Rubin");
+    /// assert_eq!(metadata.involved_people_with_role("producer"), vec!["Rick Rubin".to_string()])
+    /// ```
+    pub fn involved_people(&self) -> Vec<(String, String)> {
+        self.people_list(&self.involved_people_frame_id(false))
+    }
+    /// Returns the people involved as `role`
+    pub fn involved_people_with_role(&self, role: &str) -> Vec<String> {
+        self.involved_people()
+            .into_iter()
+            .filter(|(r, _)| r == role)
+            .map(|(_, person)| person)
+            .collect()
+    }
+    /// Replace the involved people
+    ///
+    /// Returns `false` if the list is read only
+    pub fn set_involved_people(&mut self, people: Vec<(String, String)>) -> bool {
+        self.set_people_list(self.involved_people_frame_id(false), people)
+    }
+    /// Add a person involved as `role`
+    ///
+    /// Returns `false` if the pair is already in the list or if the list is read only
+    pub fn add_involved_person(&mut self, role: &str, person: &str) -> bool {
+        self.add_people(false, role, person)
+    }
+    /// Remove the people involved as `role`, only `person` if given
+    ///
+    /// Returns `false` if no one was removed
+    pub fn remove_involved_person(&mut self, role: &str, person: Option<&str>) -> bool {
+        self.remove_people(false, role, person)
+    }
+    /// Returns the musicians as (instrument, musician) pairs, from the TMCL frame in ID3v2.4.
+    /// ID3v2.3 has no musician credits list, the IPLS frame is used as for the involved people
+    pub fn musician_credits(&self) -> Vec<(String, String)> {
+        self.people_list(&self.involved_people_frame_id(true))
+    }
+    /// Replace the musician credits
+    ///
+    /// Returns `false` if the list is read only
+    pub fn set_musician_credits(&mut self, musicians: Vec<(String, String)>) -> bool {
+        self.set_people_list(self.involved_people_frame_id(true), musicians)
+    }
+    /// Add a musician playing `instrument`
+    ///
+    /// Returns `false` if the pair is already in the list or if the list is read only
+    pub fn add_musician_credit(&mut self, instrument: &str, musician: &str) -> bool {
+        self.add_people(true, instrument, musician)
+    }
+    /// Remove the musicians playing `instrument`, only `musician` if given
+    ///
+    /// Returns `false` if no one was removed
+    pub fn remove_musician_credit(&mut self, instrument: &str, musician: Option<&str>) -> bool {
+        self.remove_people(true, instrument, musician)
+    }
+}
+
+impl ID3TAG {
+    /// Returns the table of contents of the CD stored in the music CD identifier (MCDI)
+    ///
+    /// `None` if there is no MCDI frame or if it isn't a binary TOC
+    pub fn cd_toc(&self) -> Option<CdToc> {
+        self.frames
+            .iter()
+            .find_map(|frame| frame.as_music_cd_id_frame())
+            .and_then(|frame| frame.cd_toc())
+    }
+    /// Replace the music CD identifier (MCDI) by `cd_toc`
+    ///
+    /// Returns `false` if the frame is read only
+    pub fn set_cd_toc(&mut self, cd_toc: &CdToc) -> bool {
+        self.replace_frame(MCDI, FrameValue::MCIF(MusicCdIdframe::new(cd_toc)), |frame| frame.get_frame_id() == &MCDI)
+    }
+    /// Remove the music CD identifier (MCDI)
+    pub fn remove_cd_toc(&mut self) {
+        self.remove_frames(&MCDI)
+    }
+}
+
+impl ID3TAG {
+    /// Returns the linked information frames (LINK)
+    pub fn linked_frames(&self) -> Vec<&LinkedInfoFrame> {
+        self.frames
+            .iter()
+            .filter_map(|frame| frame.as_linked_info_frame())
+            .collect()
+    }
+    /// Add a linked information frame
+    ///
+    /// Returns `false` if the same link is already in the tag
+    pub fn add_linked_frame(&mut self, link: LinkedInfoFrame) -> bool {
+        if self.linked_frames().contains(&&link) {
+            return false;
+        }
+        self.frames.push((LINK, FrameValue::LIF(link)).into());
+        self.recalcule_size();
+        true
+    }
+    /// Remove the links to the frame `frame_id`
+    ///
+    /// Returns `false` if no link was removed
+    pub fn remove_linked_frames(&mut self, frame_id: &str) -> bool {
+        self.remove_frames_matching(|frame| {
+            frame.as_linked_info_frame().is_some_and(|f| f.frame_id() == frame_id)
+        })
+    }
+    /// Remove all the linked information frames
+    pub fn remove_all_linked_frames(&mut self) {
+        self.remove_frames(&LINK)
+    }
+    /// Replace the links pointing at local files by the frames they point at
+    ///
+    /// The URL of a local file is either a `file://` URL or a path, relative to the directory of the tag's file.
+    /// All the frames of the linked file with the linked frame identifier are copied, the ID data being ignored.
+    /// The links whose file can't be read are kept.
+    ///
+    /// Returns the number of frames copied
+    pub fn resolve_linked_frames(&mut self) -> usize {
+        let directory = std::path::Path::new(&self.file_path).parent().map(|path| path.to_path_buf()).unwrap_or_default();
+        let links = self.linked_frames().into_iter().cloned().collect::<Vec<LinkedInfoFrame>>();
+        let mut count = 0;
+        for link in links {
+            let path = match link.url().strip_prefix("file://") {
+                Some(path) => std::path::PathBuf::from(path),
+                None if !link.url().contains("://") => directory.join(link.url()),
+                None => continue,
+            };
+            let frame_id = match link.frame_id().parse::<ID3FRAMEID>() {
+                Ok(frame_id) => frame_id,
+                Err(()) => continue,
+            };
+            let linked_tag = match path.to_str().and_then(ID3TAG::from_path) {
+                Some(linked_tag) => linked_tag,
+                None => continue,
+            };
+            let frames = linked_tag
+                .frames
+                .iter()
+                .filter(|frame| frame.get_frame_id() == &frame_id)
+                .filter_map(|frame| {
+                    let mut bytes = frame.get_frame_value().raw_bytes();
+                    let size = bytes.len() as u32;
+                    FrameValue::new(&mut bytes, frame_id, size)
+                })
+                .collect::<Vec<FrameValue>>();
+            if frames.is_empty() {
+                continue;
+            }
+            if self.remove_frames_matching(|frame| frame.as_linked_info_frame() == Some(&link)) {
+                count += frames.len();
+                let major_version = self.major_version;
+                self.frames.extend(frames.into_iter().map(|value| {
+                    // The frame is written with the version of this tag, not the linked one
+                    let mut frame : ID3FRAME = (frame_id, value).into();
+                    frame.adapt_to_version(major_version);
+                    frame
+                }));
+            }
+        }
+        if count > 0 {
+            self.recalcule_size();
+        }
+        count
+    }
+    /// Returns the position synchronisation (POSS)
+    pub fn position_sync(&self) -> Option<&PositionSyncFrame> {
+        self.frames.iter().find_map(|frame| frame.as_position_sync_frame())
+    }
+    /// Replace the position synchronisation (POSS)
+    ///
+    /// Returns `false` if the frame is read only
+    pub fn set_position_sync(&mut self, position: PositionSyncFrame) -> bool {
+        self.replace_frame(POSS, FrameValue::PSF(position), |frame| frame.get_frame_id() == &POSS)
+    }
+    /// Remove the position synchronisation (POSS)
+    pub fn remove_position_sync(&mut self) {
+        self.remove_frames(&POSS)
+    }
+}
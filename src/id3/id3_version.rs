@@ -0,0 +1,28 @@
+use std::fmt::Display;
+
+/// Version of an ID3v2 tag
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ID3Version {
+    /// ID3v2.3
+    #[default]
+    V2_3 = 3,
+    /// ID3v2.4
+    V2_4 = 4
+}
+
+impl ID3Version {
+    pub(crate) fn from_raw_value(value : u8) -> Option<Self> {
+        match value {
+            3 => Some(Self::V2_3),
+            4 => Some(Self::V2_4),
+            _ => None
+        }
+    }
+}
+
+impl Display for ID3Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ID3v2.{}", *self as u8)
+    }
+}
@@ -0,0 +1,295 @@
+use std::fs::OpenOptions;
+use std::io::Read;
+
+use super::code::genre_code::genre::{genre_index, genre_name, NO_GENRE};
+
+/// Size of the ID3v1 tag at the end of the file
+pub(crate) const ID3V1_SIZE : usize = 128;
+/// Size of the Enhanced TAG+ block preceding the ID3v1 tag
+pub(crate) const ENHANCED_SIZE : usize = 227;
+
+const ID3V1_IDENTIFIER : &[u8] = b"TAG";
+const ENHANCED_IDENTIFIER : &[u8] = b"TAG+";
+
+/// What to do with the ID3v1 tag when an [crate::ID3TAG] is written
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum ID3v1Mode {
+    /// Write back the ID3v1 tag as it is
+    #[default]
+    Keep,
+    /// Rebuild the ID3v1 tag from the ID3v2 frames
+    Sync,
+    /// Remove the ID3v1 tag
+    Strip
+}
+
+/// ID3v1 and ID3v1.1 tag, with the optional Enhanced TAG+ block
+///
+/// Title, artist and album up to 90 characters are stored across the ID3v1 tag (30 characters)
+/// and the Enhanced block (60 characters). Strings are encoded in ISO-8859-1.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ID3v1TAG {
+    title : String,
+    artist : String,
+    album : String,
+    year : String,
+    comment : String,
+    track : Option<u8>,
+    genre : u8,
+    speed : Option<u8>,
+    genre_description : Option<String>,
+    start_time : Option<String>,
+    end_time : Option<String>,
+}
+
+fn read_string(bytes : &[u8]) -> String {
+    bytes.iter()
+    .take_while(|byte| **byte != 0)
+    .map(|byte| *byte as char)
+    .collect::<String>()
+    .trim_end()
+    .to_string()
+}
+
+/// Encode `s` in ISO-8859-1 on exactly `size` bytes, padded with null bytes
+fn write_string(s : &str, size : usize) -> Vec<u8> {
+    let mut bytes = s.chars()
+    .map(|c| if (c as u32) <= 0xFF { c as u8 } else { b'?' })
+    .take(size)
+    .collect::<Vec<u8>>();
+    bytes.resize(size, 0);
+    bytes
+}
+
+fn split_at_char(s : &str, index : usize) -> (&str, &str) {
+    match s.char_indices().nth(index) {
+        Some((i, _)) => s.split_at(i),
+        None => (s, "")
+    }
+}
+
+impl ID3v1TAG {
+    /// Create an empty ID3v1.1 tag
+    pub fn new() -> Self {
+        Self {
+            title: String::new(),
+            artist: String::new(),
+            album: String::new(),
+            year: String::new(),
+            comment: String::new(),
+            track: None,
+            genre: NO_GENRE,
+            speed: None,
+            genre_description: None,
+            start_time: None,
+            end_time: None
+        }
+    }
+
+    /// Read the ID3v1 tag at the end of the file at `path`
+    pub fn from_path(path : &str) -> Option<Self> {
+        let mut buffer = vec![];
+        let mut file = OpenOptions::new().create(false).read(true).write(false).open(path).ok()?;
+        file.read_to_end(&mut buffer).ok()?;
+        Self::from_trailing_bytes(&buffer).map(|(tag, _)| tag)
+    }
+
+    /// Parse the ID3v1 tag ending `bytes`
+    ///
+    /// Returns the tag and the number of bytes it occupies (Enhanced block included)
+    pub(crate) fn from_trailing_bytes(bytes : &[u8]) -> Option<(Self, usize)> {
+        if bytes.len() < ID3V1_SIZE {
+            return None;
+        }
+        let tag_bytes = &bytes[(bytes.len() - ID3V1_SIZE)..];
+        if &tag_bytes[0..3] != ID3V1_IDENTIFIER {
+            return None;
+        }
+        let mut tag = Self::new();
+        tag.title = read_string(&tag_bytes[3..33]);
+        tag.artist = read_string(&tag_bytes[33..63]);
+        tag.album = read_string(&tag_bytes[63..93]);
+        tag.year = read_string(&tag_bytes[93..97]);
+        // ID3v1.1 : a null byte followed by the track number ends the comment
+        if tag_bytes[125] == 0 && tag_bytes[126] != 0 {
+            tag.comment = read_string(&tag_bytes[97..125]);
+            tag.track = Some(tag_bytes[126]);
+        } else {
+            tag.comment = read_string(&tag_bytes[97..127]);
+        }
+        tag.genre = tag_bytes[127];
+
+        let mut size = ID3V1_SIZE;
+        if bytes.len() >= ID3V1_SIZE + ENHANCED_SIZE {
+            let enhanced = &bytes[(bytes.len() - ID3V1_SIZE - ENHANCED_SIZE)..(bytes.len() - ID3V1_SIZE)];
+            if &enhanced[0..4] == ENHANCED_IDENTIFIER {
+                tag.title.push_str(&read_string(&enhanced[4..64]));
+                tag.artist.push_str(&read_string(&enhanced[64..124]));
+                tag.album.push_str(&read_string(&enhanced[124..184]));
+                tag.speed = Some(enhanced[184]).filter(|speed| *speed != 0);
+                tag.genre_description = Some(read_string(&enhanced[185..215])).filter(|s| !s.is_empty());
+                tag.start_time = Some(read_string(&enhanced[215..221])).filter(|s| !s.is_empty());
+                tag.end_time = Some(read_string(&enhanced[221..227])).filter(|s| !s.is_empty());
+                size += ENHANCED_SIZE;
+            }
+        }
+        Some((tag, size))
+    }
+
+    /// Whether the tag needs the Enhanced TAG+ block to be stored
+    pub fn is_enhanced(&self) -> bool {
+        [&self.title, &self.artist, &self.album].iter().any(|s| s.chars().count() > 30)
+        || self.speed.is_some()
+        || self.genre_description.is_some()
+        || self.start_time.is_some()
+        || self.end_time.is_some()
+    }
+
+    /// Returns the raw bytes of the tag, preceded by the Enhanced block if needed
+    pub fn as_bytes(&self) -> Vec<u8> {
+        let mut bytes = vec![];
+        let (title, title_ext) = split_at_char(&self.title, 30);
+        let (artist, artist_ext) = split_at_char(&self.artist, 30);
+        let (album, album_ext) = split_at_char(&self.album, 30);
+        if self.is_enhanced() {
+            bytes.extend_from_slice(ENHANCED_IDENTIFIER);
+            bytes.append(&mut write_string(title_ext, 60));
+            bytes.append(&mut write_string(artist_ext, 60));
+            bytes.append(&mut write_string(album_ext, 60));
+            bytes.push(self.speed.unwrap_or(0));
+            bytes.append(&mut write_string(self.genre_description.as_deref().unwrap_or_default(), 30));
+            bytes.append(&mut write_string(self.start_time.as_deref().unwrap_or_default(), 6));
+            bytes.append(&mut write_string(self.end_time.as_deref().unwrap_or_default(), 6));
+        }
+        bytes.extend_from_slice(ID3V1_IDENTIFIER);
+        bytes.append(&mut write_string(title, 30));
+        bytes.append(&mut write_string(artist, 30));
+        bytes.append(&mut write_string(album, 30));
+        bytes.append(&mut write_string(&self.year, 4));
+        match self.track {
+            Some(track) => {
+                bytes.append(&mut write_string(&self.comment, 28));
+                bytes.push(0);
+                bytes.push(track);
+            }
+            None => bytes.append(&mut write_string(&self.comment, 30))
+        }
+        bytes.push(self.genre);
+        bytes
+    }
+}
+
+impl Default for ID3v1TAG {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ID3v1TAG {
+    /// Returns the title
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+    /// Set the title (up to 90 characters with the Enhanced block)
+    pub fn set_title(&mut self, title : &str) {
+        self.title = title.chars().take(90).collect()
+    }
+    /// Returns the artist
+    pub fn artist(&self) -> &str {
+        &self.artist
+    }
+    /// Set the artist (up to 90 characters with the Enhanced block)
+    pub fn set_artist(&mut self, artist : &str) {
+        self.artist = artist.chars().take(90).collect()
+    }
+    /// Returns the album
+    pub fn album(&self) -> &str {
+        &self.album
+    }
+    /// Set the album (up to 90 characters with the Enhanced block)
+    pub fn set_album(&mut self, album : &str) {
+        self.album = album.chars().take(90).collect()
+    }
+    /// Returns the year
+    pub fn year(&self) -> &str {
+        &self.year
+    }
+    /// Set the year (4 characters)
+    pub fn set_year(&mut self, year : &str) {
+        self.year = year.chars().take(4).collect()
+    }
+    /// Returns the comment
+    pub fn comment(&self) -> &str {
+        &self.comment
+    }
+    /// Set the comment (30 characters, 28 if a track number is set)
+    pub fn set_comment(&mut self, comment : &str) {
+        self.comment = comment.chars().take(30).collect()
+    }
+    /// Returns the track number (ID3v1.1)
+    pub fn track(&self) -> Option<u8> {
+        self.track
+    }
+    /// Set the track number, which turns the tag into an ID3v1.1 tag
+    ///
+    /// A track number of 0 is not representable and removes the track
+    pub fn set_track(&mut self, track : Option<u8>) {
+        self.track = track.filter(|track| *track != 0)
+    }
+    /// Returns the genre byte
+    pub fn genre(&self) -> u8 {
+        self.genre
+    }
+    /// Returns the name of the genre
+    pub fn genre_name(&self) -> Option<&'static str> {
+        genre_name(self.genre)
+    }
+    /// Set the genre byte (255 for no genre)
+    pub fn set_genre(&mut self, genre : u8) {
+        self.genre = genre
+    }
+    /// Set the genre from its name
+    ///
+    /// Returns `false` and leaves the genre unchanged if the name isn't an ID3v1 genre
+    pub fn set_genre_name(&mut self, genre : &str) -> bool {
+        match genre_index(genre) {
+            Some(index) => {
+                self.genre = index;
+                true
+            }
+            None => false
+        }
+    }
+    /// Returns the speed of the Enhanced block (1 slow, 2 medium, 3 fast, 4 hardcore)
+    pub fn speed(&self) -> Option<u8> {
+        self.speed
+    }
+    /// Set the speed of the Enhanced block
+    pub fn set_speed(&mut self, speed : Option<u8>) {
+        self.speed = speed.filter(|speed| *speed != 0)
+    }
+    /// Returns the free text genre of the Enhanced block
+    pub fn genre_description(&self) -> Option<&str> {
+        self.genre_description.as_deref()
+    }
+    /// Set the free text genre of the Enhanced block (30 characters)
+    pub fn set_genre_description(&mut self, genre : Option<&str>) {
+        self.genre_description = genre.map(|genre| genre.chars().take(30).collect())
+    }
+    /// Returns the start of the music in the Enhanced block (mmm:ss)
+    pub fn start_time(&self) -> Option<&str> {
+        self.start_time.as_deref()
+    }
+    /// Set the start of the music in the Enhanced block (mmm:ss)
+    pub fn set_start_time(&mut self, time : Option<&str>) {
+        self.start_time = time.map(|time| time.chars().take(6).collect())
+    }
+    /// Returns the end of the music in the Enhanced block (mmm:ss)
+    pub fn end_time(&self) -> Option<&str> {
+        self.end_time.as_deref()
+    }
+    /// Set the end of the music in the Enhanced block (mmm:ss)
+    pub fn set_end_time(&mut self, time : Option<&str>) {
+        self.end_time = time.map(|time| time.chars().take(6).collect())
+    }
+}
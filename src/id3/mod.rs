@@ -3,4 +3,12 @@ pub (crate) mod id3_header_flag;
 pub (crate) mod id3_tag;
 pub (crate) mod id3_frame_value;
 pub (crate) mod code;
+pub (crate) mod id3_version;
+pub (crate) mod id3v1_tag;
+pub (crate) mod id3_extended_header;
+pub (crate) mod id3_padding;
+pub (crate) mod id3_lrc;
+pub (crate) mod id3_replay_gain;
+pub (crate) mod id3_serato;
+pub (crate) mod id3_private;
 pub  mod id3_frameid;
\ No newline at end of file
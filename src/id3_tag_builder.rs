@@ -1,4 +1,4 @@
-//! Allow to create an ID3v2.3 tag from scratch
+//! Allow to create an ID3v2.3 or ID3v2.4 tag from scratch
 use std::{
     fs::{File, OpenOptions},
     io::{Error, Read, Write, Seek, SeekFrom},
@@ -10,6 +10,9 @@ use crate::id3::{
     code::picture_code::picture_type::PictureType,
     id3_frameid::{ID3FRAMEID, ID3TEXTFRAMEID, ID3TEXTFRAMEID::*},
     id3_tag::ID3TAG,
+    id3_version::ID3Version,
+    id3_extended_header::ID3ExtendedHeader,
+    id3_padding::PaddingPolicy,
 };
 
 use crate::{
@@ -18,7 +21,7 @@ use crate::{
 };
 
 
-/// Builder for ID3v2.3 tag
+/// Builder for ID3v2.3 and ID3v2.4 tag
 pub struct ID3TagBuilder {
     id3_tag: ID3TAG,
 }
@@ -30,6 +33,38 @@ impl ID3TagBuilder {
             id3_tag: ID3TAG::new_empty_tag(),
         }
     }
+    /// Set the version of the tag (ID3v2.3 by default)
+    ///
+    /// Frames already added are converted to the new version
+    ///
+    /// # Examples
+    /// ```
+    /// use tag_edit::{ID3TagBuilder, ID3Version};
+    /// let mut tag_builder = ID3TagBuilder::new();
+    /// tag_builder
+    /// .set_version(ID3Version::V2_4)
+    /// .set_title("A title")
+    /// .set_year(2021);
+    /// ```
+    pub fn set_version(&mut self, version: ID3Version) -> &mut Self {
+        self.id3_tag.set_version(version);
+        self
+    }
+    /// Apply the unsynchronisation scheme to the tag, for old players unaware of ID3v2 tags
+    pub fn set_unsynchronisation(&mut self, unsynchronisation: bool) -> &mut Self {
+        self.id3_tag.set_unsynchronisation(unsynchronisation);
+        self
+    }
+    /// Set the padding written after the frames
+    pub fn set_padding_policy(&mut self, padding_policy: PaddingPolicy) -> &mut Self {
+        self.id3_tag.set_padding_policy(padding_policy);
+        self
+    }
+    /// Write an extended header with the tag
+    pub fn set_extended_header(&mut self, extended_header: ID3ExtendedHeader) -> &mut Self {
+        self.id3_tag.set_extended_header(Some(extended_header));
+        self
+    }
     /// Add a text frame to the tag.
     /// Replace the content if the text frame already exists
     ///
@@ -100,10 +135,11 @@ impl ID3TagBuilder {
     pub fn set_title(&mut self, content: &str) -> &mut Self {
         self.add_text_frame(TIT2, content)
     }
-    /// Set the track year (TYER)
+    /// Set the track year (TYER or TDRC in ID3v2.4)
     ///
     pub fn set_year(&mut self, content: i16) -> &mut Self {
-        self.add_text_frame(TYER, content.to_string().as_str())
+        self.id3_tag.set_year(content);
+        self
     }
     /// Set the track position in the album (TRCK)
     ///
@@ -1,5 +1,5 @@
 //! A library to read and write tag from audio files.
-//! Currently, only ID3v2.3 and Flac supported
+//! Currently, only ID3v2.3, ID3v2.4 (ID3v2.2 read only) and Flac supported
 //! 
 //! 
 //! 
@@ -72,11 +72,35 @@
 
 pub use crate::id3_tag_builder::ID3TagBuilder;
 pub use crate::tag_error::TagError;
-pub use crate::id3::id3_frameid::ID3TEXTFRAMEID;
+pub use crate::id3::id3_frameid::{ID3TEXTFRAMEID, ID3URLFRAMEID};
 pub use crate::util::file_format::PictureFormat;
+pub use crate::util::cd_toc::{CdToc, CdTocTrack, SECTORS_PER_SECOND};
 pub use crate::flac::flac_tag::FlacTag;
 pub use crate::id3::code::picture_code::picture_type::PictureType;
+pub use crate::id3::code::event_timing_code::time_stamp_format::TimeStampFormat;
+pub use crate::id3::code::event_timing_code::event_type::EventType;
+pub use crate::id3::code::text_code::content_type::TextContent;
+pub use crate::id3::code::rating_code::rating_scale::RatingScale;
+pub use crate::id3::code::volume_code::channel_type::ChannelType;
+pub use crate::id3::code::volume_code::interpolation_method::InterpolationMethod;
+pub use crate::id3::id3_replay_gain::{ReplayGain, ReplayGainScope};
+pub use crate::id3::id3_private::PrivateContent;
+pub use crate::id3::id3_serato::{
+    SeratoAutotags, SeratoBeatGrid, SeratoCue, SeratoLoop, SeratoMarkers2, SeratoOverview
+};
+pub use crate::id3::id3_frame_value::{
+    ChannelAdjustment, Equalisation2Frame, EqualiisationFrame, EventTimingFrame, GeneralEncapsulatedObjectFrame,
+    LinkedInfoFrame, LocationLookupTableFrame, PopularimeterFrame, PositionSyncFrame, PrivateFrame, RelativeVolumeAdjustementFrame, ReverbFrame,
+    SyncLyricsFrame, SyncTempoCodeFrame, UniqueFileIdentifierFrame, UserUrlFrame, BEAT_FREE, SINGLE_BEAT_STROKE
+};
 pub use crate::id3::id3_tag::ID3TAG;
+pub use crate::id3::id3_version::ID3Version;
+pub use crate::id3::id3v1_tag::{ID3v1Mode, ID3v1TAG};
+pub use crate::id3::id3_header_flag::FrameStatusFlags;
+pub use crate::id3::id3_padding::{PaddingPolicy, WriteStrategy};
+pub use crate::id3::id3_extended_header::{
+    ID3ExtendedHeader, ImageSizeRestriction, TagRestrictions, TagSizeRestriction, TextSizeRestriction
+};
 
 
 pub (crate) mod id3_tag_builder;
@@ -99,7 +123,7 @@ mod test {
     
     use std::{io::{Error, Read, Write}, fs::OpenOptions, collections::HashMap};
 
-    use crate::{id3_tag_builder::ID3TagBuilder, FlacTag, ID3TAG};
+    use crate::{id3_tag_builder::ID3TagBuilder, FlacTag, ID3TAG, ID3Version, ID3v1Mode};
     use crate::PictureType;
     use crate::PictureType::*;
 
@@ -138,6 +162,655 @@ mod test {
   
     }
 
+    #[test]
+    fn id3v24_round_trip() {
+        let mut tag = ID3TAG::new_empty_tag();
+        tag.set_version(ID3Version::V2_4);
+        tag.set_title("刹那の果実");
+        tag.set_year(2015);
+        let mut bytes = tag.as_bytes();
+        assert_eq!(bytes[3], 4);
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.version(), ID3Version::V2_4);
+        assert_eq!(tag.title(), Some("刹那の果実".to_string()));
+        assert_eq!(tag.recording_time(), Some("2015".to_string()));
+        tag.set_version(ID3Version::V2_3);
+        tag.set_user_text("説明", "テキスト");
+        let mut bytes = tag.as_bytes();
+        // The UTF-16 strings of ID3v2.3 begin with a byte order mark
+        let title = bytes.windows(4).position(|w| w == b"TIT2").unwrap();
+        assert_eq!(&bytes[(title + 10)..(title + 13)], &[1, 0xFF, 0xFE]);
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.title(), Some("刹那の果実".to_string()));
+        assert_eq!(tag.get_user_text("説明"), Some("テキスト".to_string()));
+        assert_eq!(tag.year(), Some(2015));
+        assert_eq!(tag.recording_time(), None);
+
+        // The frames only defined by ID3v2.4 are dropped
+        let v24_only = ["TDEN", "TDRL", "TDTG", "TMOO", "TPRO", "TSOA", "TSOP", "TSOT", "TSST", "ASPI", "SEEK", "SIGN", "EQU2"];
+        let mut frames = vec![];
+        for frame_id in v24_only.iter() {
+            frames.extend_from_slice(frame_id.as_bytes());
+            frames.extend_from_slice(b"\x00\x00\x00\x05\x00\x00\x00abcd");
+        }
+        frames.extend_from_slice(b"TIT2\x00\x00\x00\x06\x00\x00\x00Title");
+        let mut bytes = b"ID3\x04\x00\x00".to_vec();
+        bytes.extend_from_slice(&crate::util::function::synchsafe(frames.len() as u32).to_be_bytes());
+        bytes.append(&mut frames);
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        tag.set_version(ID3Version::V2_3);
+        let bytes = tag.as_bytes();
+        assert!(bytes.windows(4).any(|w| w == b"TIT2"));
+        for frame_id in v24_only.iter() {
+            assert!(!bytes.windows(4).any(|w| w == frame_id.as_bytes()), "{}", frame_id);
+        }
+    }
+
+    #[test]
+    fn empty_frames() {
+        let mut frames = vec![];
+        for frame_id in ["ETCO", "SYTC", "TXXX"].iter() {
+            frames.extend_from_slice(frame_id.as_bytes());
+            frames.extend_from_slice(&[0u8; 6]);
+        }
+        frames.extend_from_slice(b"TIT2\x00\x00\x00\x06\x00\x00\x00Title");
+        let mut bytes = b"ID3\x03\x00\x00\x00\x00\x00".to_vec();
+        bytes.push(frames.len() as u8);
+        bytes.append(&mut frames);
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.title(), Some("Title".to_string()));
+        assert_eq!(tag.event_timing_codes(), None);
+        let mut bytes = tag.as_bytes();
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.title(), Some("Title".to_string()));
+    }
+
+    #[test]
+    fn id3v22_upgrade() {
+        let mut frames = vec![];
+        frames.extend_from_slice(b"TT2\x00\x00\x06\x00Title");
+        frames.extend_from_slice(b"PIC\x00\x00\x09\x00JPG\x03\x00\xFF\xD8\xFF");
+        let mut bytes = b"ID3\x02\x00\x00\x00\x00\x00".to_vec();
+        bytes.push(frames.len() as u8 + 4);
+        bytes.append(&mut frames);
+        bytes.append(&mut vec![0u8; 4]);
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.version(), ID3Version::V2_3);
+        assert_eq!(tag.title(), Some("Title".to_string()));
+        assert_eq!(tag.attached_pictures(), vec![&vec![0xFF, 0xD8, 0xFF]]);
+        let bytes = tag.as_bytes();
+        assert_eq!(bytes[3], 3);
+        assert!(bytes.windows(10).any(|w| w == b"image/jpeg"));
+
+        // Many unknown frames, a link to a frame and a frame which can't be parsed
+        let mut frames = b"XYZ\x00\x00\x00".repeat(50_000);
+        frames.extend_from_slice(b"LNK\x00\x00\x0DTT2other.mp3\x00");
+        frames.extend_from_slice(b"POP\x00\x00\x03abc");
+        let mut bytes = b"ID3\x02\x00\x00".to_vec();
+        bytes.extend_from_slice(&crate::util::function::synchsafe(frames.len() as u32).to_be_bytes());
+        bytes.append(&mut frames);
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        let links = tag.linked_frames();
+        assert_eq!(links.len(), 1);
+        assert_eq!((links[0].frame_id(), links[0].url()), ("TIT2", "other.mp3"));
+        let bytes = tag.as_bytes();
+        assert!(bytes.windows(13).any(|w| w == b"POPM\x00\x00\x00\x03\x00\x00abc"));
+    }
+
+    #[test]
+    fn id3v1_sync_and_strip() {
+        let mut tag = ID3TAG::new_empty_tag();
+        tag.set_title("A title long enough to need the enhanced block");
+        tag.set_genre("Rock");
+        tag.set_track_position(3, Some(12));
+        tag.set_id3v1_mode(ID3v1Mode::Sync);
+        let mut bytes = tag.as_bytes();
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        let id3v1 = tag.id3v1().unwrap();
+        assert_eq!(id3v1.title(), "A title long enough to need the enhanced block");
+        assert_eq!(id3v1.track(), Some(3));
+        assert_eq!(id3v1.genre_name(), Some("Rock"));
+        let size = tag.as_bytes().len();
+        tag.set_id3v1_mode(ID3v1Mode::Strip);
+        assert_eq!(tag.as_bytes().len(), size - 128 - 227);
+    }
+
+    #[test]
+    fn unsynchronisation_round_trip() {
+        let picture = vec![0xFF, 0xE0, 0xFF, 0x00, 0x12, 0xFF];
+        for version in [ID3Version::V2_3, ID3Version::V2_4] {
+            let mut tag = ID3TAG::new_empty_tag();
+            tag.set_version(version);
+            tag.add_picture(crate::PictureFormat::JPEG, &picture, Some(CoverFront), None);
+            tag.set_unsynchronisation(true);
+            let mut bytes = tag.as_bytes();
+            assert!(!bytes[10..].windows(2).any(|w| w[0] == 0xFF && w[1] >= 0xE0));
+            let tag = ID3TAG::new("", &mut bytes).unwrap();
+            assert_eq!(tag.attached_pictures(), vec![&picture]);
+        }
+    }
+
+    #[test]
+    fn extended_header_crc() {
+        for version in [ID3Version::V2_3, ID3Version::V2_4] {
+            let mut tag = ID3TAG::new_empty_tag();
+            tag.set_version(version);
+            tag.set_title("A title");
+            let mut extended_header = crate::ID3ExtendedHeader::new();
+            extended_header.set_crc(true);
+            extended_header.set_restrictions(Some(crate::TagRestrictions {
+                text_size: crate::TextSizeRestriction::Characters30,
+                ..Default::default()
+            }));
+            tag.set_extended_header(Some(extended_header));
+            let mut bytes = tag.as_bytes();
+            let tag = ID3TAG::new("", &mut bytes).unwrap();
+            assert_eq!(tag.is_crc_valid(), Some(true));
+            assert_eq!(tag.title(), Some("A title".to_string()));
+            let restrictions = tag.extended_header().unwrap().restrictions();
+            if version == ID3Version::V2_4 {
+                assert_eq!(restrictions.unwrap().text_size, crate::TextSizeRestriction::Characters30);
+            } else {
+                assert_eq!(restrictions, None);
+            }
+        }
+    }
+
+    #[test]
+    fn zlib_compressed_frames() {
+        // Dynamic Huffman block produced by zlib
+        let compressed = [
+            0x78, 0xDA, 0x6D, 0x8E, 0xE1, 0x0E, 0x80, 0x20, 0x08, 0x84, 0x5F, 0xA5, 0x57, 0xC3, 0x75, 0xCB,
+            0x36, 0x2D, 0xB7, 0xFC, 0xC5, 0xD3, 0x1B, 0x91, 0xA5, 0x8C, 0x1F, 0x7C, 0x30, 0xEF, 0x38, 0x41,
+            0xA5, 0x05, 0x77, 0x05, 0xC1, 0x8A, 0x64, 0xB8, 0x51, 0xCE, 0x7D, 0x46, 0xB9, 0xF6, 0x74, 0x1E,
+            0x8F, 0x9D, 0x05, 0x35, 0x62, 0x7C, 0x7E, 0x7B, 0xF8, 0x25, 0x3B, 0xF6, 0xD2, 0x54, 0x15, 0x46,
+            0x1A, 0xBB, 0xDA, 0x28, 0x95, 0x48, 0x73, 0xBA, 0x0A, 0x70, 0x6F, 0xE5, 0x6F, 0x7D, 0x5E, 0xEC,
+            0x9D, 0xBD, 0x4F, 0x19, 0x26, 0x45, 0xE9, 0xC4, 0x8C, 0x26, 0xE7, 0x64, 0x41, 0x03, 0x91, 0x4E,
+            0x7A, 0xB9
+        ];
+        let words = ["alpha", "beta", "gamma", "delta", "epsilon", "zeta", "eta", "theta"];
+        let mut x = 1u64;
+        let text = (0..60).map(|_| {
+            x = (x * 1103515245 + 12345) % (1 << 31);
+            words[((x >> 16) % 8) as usize]
+        }).collect::<Vec<&str>>().join(" ");
+        assert_eq!(crate::util::zlib::inflate(&compressed, text.len()), Some(text.clone().into_bytes()));
+        assert_eq!(crate::util::zlib::inflate(&compressed, text.len() - 1), None);
+        let deflated = crate::util::zlib::deflate(text.as_bytes());
+        assert_eq!(crate::util::zlib::inflate(&deflated, text.len()), Some(text.clone().into_bytes()));
+        assert_eq!(crate::util::zlib::inflate(&deflated, 100), None);
+        assert_eq!(crate::util::zlib::inflate(&crate::util::zlib::deflate(&[0u8; 100_000]), 1_000), None);
+
+        let picture = vec![0x42u8; 10_000];
+        let mut tag = ID3TAG::new_empty_tag();
+        tag.add_picture(crate::PictureFormat::PNG, &picture, Some(CoverFront), None);
+        let size = tag.as_bytes().len();
+        tag.set_compression_threshold(Some(1024));
+        let mut bytes = tag.as_bytes();
+        assert!(bytes.len() < size - 9_000);
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.attached_pictures(), vec![&picture]);
+    }
+
+    #[test]
+    fn unknown_frames_preserved() {
+        let unknown = b"GRP1\x00\x00\x00\x06\x20\x00\x00Group".to_vec();
+        let mut frames = unknown.clone();
+        frames.extend_from_slice(b"TIT2\x00\x00\x00\x06\x00\x00\x00Title");
+        let mut bytes = b"ID3\x03\x00\x00\x00\x00\x00".to_vec();
+        bytes.push(frames.len() as u8 + 4);
+        bytes.append(&mut frames);
+        bytes.append(&mut vec![0u8; 4]);
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.title(), Some("Title".to_string()));
+        let bytes = tag.as_bytes();
+        assert_eq!(&bytes[10..(10 + unknown.len())], unknown.as_slice());
+
+        // Compressed unknown frame : decompressed size followed by the body, written back as it is
+        let unknown = b"XYZW\x00\x00\x00\x08\x00\x80\x00\x00\x00\x05ABCD".to_vec();
+        let mut bytes = b"ID3\x03\x00\x00\x00\x00\x00".to_vec();
+        bytes.push(unknown.len() as u8 + 4);
+        bytes.extend_from_slice(&unknown);
+        bytes.append(&mut vec![0u8; 4]);
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        let bytes = tag.as_bytes();
+        assert_eq!(&bytes[10..(10 + unknown.len())], unknown.as_slice());
+    }
+
+    #[test]
+    fn frame_status_flags() {
+        let mut tag = ID3TAG::new_empty_tag();
+        tag.set_title("Title");
+        tag.set_album("Album");
+        tag.set_artist("Artist");
+        tag.set_year(2015);
+        let mut bytes = tag.as_bytes();
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        let read_only = crate::FrameStatusFlags { read_only: true, ..Default::default() };
+        assert!(tag.set_frame_status_flags("TIT2", read_only));
+        assert!(tag.set_frame_status_flags("TALB", crate::FrameStatusFlags { discard_on_tag_alteration: true, ..Default::default() }));
+        assert!(tag.set_frame_status_flags("TPE1", crate::FrameStatusFlags { discard_on_file_alteration: true, ..Default::default() }));
+        let mut bytes = tag.as_bytes();
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.frame_status_flags("TIT2"), vec![read_only]);
+        // Changing the padding or the version and removing absent frames aren't tag alterations
+        tag.set_padding_policy(crate::PaddingPolicy::Aligned4KiB);
+        tag.set_version(ID3Version::V2_4);
+        tag.set_version(ID3Version::V2_3);
+        assert!(!tag.remove_user_text("absent"));
+        let mut padded = tag.as_bytes();
+        assert_eq!(ID3TAG::new("", &mut padded).unwrap().album(), Some("Album".to_string()));
+        tag.set_title("Other title");
+        assert_eq!(tag.title(), Some("Title".to_string()));
+        tag.file_altered();
+        assert_eq!(tag.artist(), None);
+        let mut bytes = tag.as_bytes();
+        assert_eq!(tag.total_size() as usize, bytes.len());
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.album(), None);
+        tag.set_force_read_only_edits(true);
+        tag.set_title("Other title");
+        assert_eq!(tag.title(), Some("Other title".to_string()));
+    }
+
+    #[test]
+    fn in_place_rewrite() -> Result<(), Error> {
+        let path = std::env::temp_dir().join("tag_edit_in_place.mp3");
+        let path = path.to_str().unwrap();
+        let mut tag = ID3TAG::new_empty_tag();
+        tag.set_padding_policy(crate::PaddingPolicy::Aligned4KiB);
+        tag.set_title("A title");
+        let mut bytes = tag.as_bytes();
+        assert_eq!(bytes.len(), 4096);
+        bytes.extend_from_slice(&[0xFF, 0xFB, 0x90, 0x00]);
+        std::fs::write(path, &bytes)?;
+
+        let mut tag = ID3TAG::from_path(path).unwrap();
+        tag.set_title("Another title");
+        assert_eq!(tag.overwrite_tag()?, crate::WriteStrategy::InPlace);
+        let mut tag = ID3TAG::from_path(path).unwrap();
+        assert_eq!(tag.title(), Some("Another title".to_string()));
+        tag.add_picture(crate::PictureFormat::PNG, &vec![1u8; 5000], Some(CoverFront), None);
+        tag.set_padding_policy(crate::PaddingPolicy::Fixed(100));
+        assert_eq!(tag.overwrite_tag()?, crate::WriteStrategy::FullRewrite);
+        let bytes = std::fs::read(path)?;
+        assert!(bytes.ends_with(&[0, 0, 0xFF, 0xFB, 0x90, 0x00]));
+        assert_eq!(ID3TAG::from_path(path).unwrap().title(), Some("Another title".to_string()));
+        std::fs::remove_file(path)
+    }
+
+    #[test]
+    fn synced_lyrics_lrc() {
+        let lrc = "[ar:An artist]\n[offset:100]\n[00:12.50][01:02.00]刹那\n[00:05.10]First line\n";
+        let mut tag = ID3TAG::new_empty_tag();
+        tag.add_synced_lyrics(crate::SyncLyricsFrame::from_lrc(lrc, "jpn", "")).unwrap();
+        assert!(tag.add_synced_lyrics(crate::SyncLyricsFrame::from_lrc(lrc, "jpn", "")).is_err());
+        let mut bytes = tag.as_bytes();
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        let lyrics = tag.synced_lyrics();
+        assert_eq!(lyrics[0].language(), "jpn");
+        assert_eq!(lyrics[0].content_type(), crate::TextContent::Lyrics);
+        assert_eq!(lyrics[0].lyrics(), &vec![
+            ("First line".to_string(), 5000), ("刹那".to_string(), 12400), ("刹那".to_string(), 61900)
+        ]);
+        assert_eq!(lyrics[0].to_lrc().unwrap(), "[00:05.00]First line\n[00:12.40]刹那\n[01:01.90]刹那\n");
+    }
+
+    #[test]
+    fn ratings_and_play_count() {
+        let mut tag = ID3TAG::new_empty_tag();
+        assert!(tag.set_stars("no@email", crate::RatingScale::MediaMonkey, 3.5));
+        assert_eq!(tag.rating("no@email"), Some(186));
+        assert!(tag.increment_play_count(Some("no@email")));
+        tag.set_play_count(u32::MAX as u64);
+        tag.increment_play_count(None);
+        let mut bytes = tag.as_bytes();
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.play_count(), Some(u32::MAX as u64 + 1));
+        assert_eq!(tag.popularimeters()[0].counter(), Some(1));
+        assert_eq!(tag.stars("no@email", crate::RatingScale::MediaMonkey), Some(3.5));
+        assert_eq!(tag.stars("no@email", crate::RatingScale::WindowsMediaPlayer), Some(4.0));
+        assert_eq!(crate::RatingScale::Foobar2000.stars(50), Some(1.0));
+
+        // A read only popularimeter leaves the play counter untouched
+        let mut tag = tag;
+        let read_only = crate::FrameStatusFlags { read_only: true, ..Default::default() };
+        assert!(tag.set_frame_status_flags("POPM", read_only));
+        assert!(!tag.increment_play_count(Some("no@email")));
+        assert_eq!(tag.play_count(), Some(u32::MAX as u64 + 1));
+        assert!(tag.increment_play_count(Some("other@email")));
+        assert_eq!(tag.play_count(), Some(u32::MAX as u64 + 2));
+    }
+
+    #[test]
+    fn event_timing_codes() {
+        use crate::EventType::*;
+        let mut tag = ID3TAG::new_empty_tag();
+        tag.set_event_timing_codes(crate::TimeStampFormat::Milliseconds, vec![
+            (OutroStart, 215_000), (IntroStart, 0), (NotPredefinedSync(3), 1000), (Extended(vec![0xFF, 0x01]), 2000)
+        ]);
+        let mut bytes = tag.as_bytes();
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        let etco = tag.event_timing_codes().unwrap();
+        assert_eq!(etco.events(), &vec![
+            (IntroStart, 0), (NotPredefinedSync(3), 1000), (Extended(vec![0xFF, 0x01]), 2000), (OutroStart, 215_000)
+        ]);
+        assert_eq!(tag.event_timestamp(&OutroStart), Some(215_000));
+    }
+
+    #[test]
+    fn tempo_map() {
+        let mut tag = ID3TAG::new_empty_tag();
+        tag.add_text_frame(crate::ID3TEXTFRAMEID::TLEN, "40000");
+        tag.set_tempo_map(crate::TimeStampFormat::Milliseconds, vec![
+            (300, 10_000), (100, 0), (crate::BEAT_FREE, 30_000)
+        ]);
+        assert_eq!(tag.bpm(), Some("233".to_string()));
+        let mut bytes = tag.as_bytes();
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.tempo_map().unwrap().tempos(), &vec![(100, 0), (300, 10_000), (0, 30_000)]);
+        assert_eq!(tag.tempo_at(5_000), Some(100));
+        assert_eq!(tag.tempo_at(10_000), Some(300));
+        assert_eq!(tag.tempo_at(35_000), None);
+    }
+
+    #[test]
+    fn location_lookup_table() {
+        // MPEG-1 layer III, 44.1 kHz : 128 kbit/s frames of 417 bytes and 320 kbit/s frames of 1044 bytes
+        let mut music = vec![0u8; 3];
+        for i in 0..10 {
+            let (bitrate, size) = if i % 3 == 0 { (0xE0, 1044) } else { (0x90, 417) };
+            let mut frame = vec![0u8; size];
+            frame[0..4].copy_from_slice(&[0xFF, 0xFB, bitrate, 0x00]);
+            music.append(&mut frame);
+        }
+        let mut bytes = ID3TAG::new_empty_tag().as_bytes();
+        bytes.append(&mut music);
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert!(tag.generate_location_lookup_table(2));
+        let mut bytes = tag.as_bytes();
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        let mllt = tag.location_lookup_table().unwrap();
+        assert_eq!(mllt.bytes_between_reference(), 834);
+        assert_eq!((mllt.bits_for_bytes_deviation() + mllt.bits_for_milliseconds_deviation()) % 4, 0);
+        assert_eq!(mllt.references(), vec![(0, 0), (1461, 52), (2922, 104), (3756, 157), (5217, 209)]);
+        assert_eq!(mllt.seek(110), (2922, 104));
+
+        // CBR, MPEG-1 layer III, 48 kHz : 128 kbit/s frames of 384 bytes and 24 ms, an odd number of deviations
+        let mut bytes = ID3TAG::new_empty_tag().as_bytes();
+        for _ in 0..7 {
+            let mut frame = vec![0u8; 384];
+            frame[0..4].copy_from_slice(&[0xFF, 0xFB, 0x94, 0x00]);
+            bytes.append(&mut frame);
+        }
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert!(tag.generate_location_lookup_table(2));
+        let mut bytes = tag.as_bytes();
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        let mllt = tag.location_lookup_table().unwrap();
+        assert_eq!(mllt.deviations(), &vec![(0, 0); 3]);
+        assert_eq!(mllt.references(), vec![(0, 0), (768, 48), (1536, 96), (2304, 144)]);
+        assert_eq!(mllt.seek(1000), (2304, 144));
+    }
+
+    #[test]
+    fn replay_gain_and_relative_volume() {
+        use crate::{ReplayGain, ReplayGainScope::*};
+        let mut tag = ID3TAG::new_empty_tag();
+        tag.set_replay_gain(Track, ReplayGain { gain: -6.5, peak: Some(0.988312) });
+        tag.set_replay_gain(Album, ReplayGain { gain: 1.25, peak: None });
+        let mut bytes = tag.as_bytes();
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.get_user_text("replaygain_track_gain"), Some("-6.50 dB".to_string()));
+        assert_eq!(tag.relative_volume_adjustments().len(), 1);
+        let rvad = tag.replay_gain_from_relative_volume(Track).unwrap();
+        assert!((rvad.gain + 6.5).abs() < 0.001 && (rvad.peak.unwrap() - 0.988312).abs() < 0.0001);
+        assert_eq!(tag.replay_gain(Album), Some(ReplayGain { gain: 1.25, peak: None }));
+
+        tag.set_version(ID3Version::V2_4);
+        tag.replay_gain_to_relative_volume();
+        let mut bytes = tag.as_bytes();
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        let album = tag.relative_volume_adjustment("album").unwrap();
+        assert_eq!(album.channel(crate::ChannelType::MasterVolume).unwrap().adjustment, 1.25);
+        assert!((tag.replay_gain_from_relative_volume(Track).unwrap().gain + 6.5).abs() < 0.001);
+    }
+
+    #[test]
+    fn equalisation_and_reverb() {
+        use crate::{Equalisation2Frame, EqualiisationFrame, InterpolationMethod, ReverbFrame};
+        let mut tag = ID3TAG::new_empty_tag();
+        tag.set_equalisation(EqualiisationFrame::new(12, vec![(1000, -300), (100, 4095)]));
+        tag.set_reverb(ReverbFrame { reverb_left: 300, bounces_right: 255, premix_right_to_left: 64, ..Default::default() });
+        let mut bytes = tag.as_bytes();
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.equalisation().unwrap().bands(), &vec![(100, 4095), (1000, -300)]);
+        // The bands are converted to points without identification, the full scale being a factor of 2
+        tag.set_version(ID3Version::V2_4);
+        assert!(tag.equalisation().is_none());
+        let converted = tag.equalisations2()[0];
+        assert_eq!((converted.identification(), converted.interpolation()), ("", InterpolationMethod::Band));
+        assert_eq!(converted.points().iter().map(|(frequency, _)| *frequency).collect::<Vec<f32>>(), vec![100.0, 1000.0]);
+        assert!((converted.points()[0].1 - 6.0206).abs() < 0.01 && converted.points()[1].1 < 0.0);
+        tag.set_equalisation2(Equalisation2Frame::new(InterpolationMethod::Linear, "room", vec![(440.5, -3.25), (60.0, 1.5)]));
+        let mut bytes = tag.as_bytes();
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        let equ2 = tag.equalisations2()[1];
+        assert_eq!((equ2.identification(), equ2.interpolation()), ("room", InterpolationMethod::Linear));
+        assert_eq!(equ2.points(), &vec![(60.0, 1.5), (440.5, -3.25)]);
+        let reverb = tag.reverb().unwrap();
+        assert_eq!((reverb.reverb_left, reverb.bounces_right, reverb.premix_right_to_left), (300, 255, 64));
+        // The points without identification are converted back
+        tag.set_version(ID3Version::V2_3);
+        assert!(tag.equalisations2().is_empty());
+        let bands = tag.equalisation().unwrap().bands();
+        assert_eq!((bands[0], bands[1].0), ((100, 65535), 1000));
+        assert!(bands[1].1 < 0);
+    }
+
+    #[test]
+    fn encapsulated_objects() {
+        use crate::GeneralEncapsulatedObjectFrame;
+        let mut tag = ID3TAG::new_empty_tag();
+        let markers = GeneralEncapsulatedObjectFrame::new("application/octet-stream", "", "Serato Markers2", vec![1, 1, 0, 0]);
+        tag.set_encapsulated_object(markers);
+        tag.set_encapsulated_object(GeneralEncapsulatedObjectFrame::new("text/plain", "notes.txt", "Notés", b"cue".to_vec()));
+        tag.set_encapsulated_object(GeneralEncapsulatedObjectFrame::new("application/octet-stream", "", "Serato Markers2", vec![2]));
+        let mut bytes = tag.as_bytes();
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.encapsulated_objects().len(), 2);
+        assert_eq!(tag.encapsulated_object("Serato Markers2").unwrap().data(), &vec![2]);
+        let notes = tag.encapsulated_object("Notés").unwrap();
+        assert_eq!((notes.mime_type(), notes.filename(), notes.data().as_slice()), ("text/plain", "notes.txt", &b"cue"[..]));
+        assert!(tag.remove_encapsulated_object("Notés"));
+        assert!(tag.encapsulated_object("Notés").is_none());
+    }
+
+    #[test]
+    fn serato_objects() {
+        use crate::{SeratoAutotags, SeratoBeatGrid, SeratoCue, SeratoLoop, SeratoMarkers2};
+        let mut tag = ID3TAG::new_empty_tag();
+        let mut markers = SeratoMarkers2 { track_color: Some(0xFFFFFF), bpm_lock: Some(false), ..Default::default() };
+        markers.cues.push(SeratoCue { index: 0, position: 1500, color: 0xCC0000, name: "Drop".into() });
+        markers.loops.push(SeratoLoop { index: 0, start: 2000, end: 6000, color: 0x27AAE1, locked: true, name: String::new() });
+        tag.set_serato_markers(&markers);
+        let beatgrid = SeratoBeatGrid { markers: vec![(0.05, 64)], terminal_marker: Some((30.1, 128.0)) };
+        tag.set_serato_beatgrid(&beatgrid);
+        tag.set_serato_autotags(&SeratoAutotags { bpm: 128.0, auto_gain: -3.257, gain: 0.0 });
+        let object = tag.encapsulated_object(SeratoMarkers2::DESCRIPTION).unwrap().data();
+        assert!(object.starts_with(b"\x01\x01AQFDT0xPUgAAAAAEAP///0NVRQAAAAAR") && object.len() == 470);
+        let mut bytes = tag.as_bytes();
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.serato_markers().unwrap(), markers);
+        assert_eq!(tag.serato_beatgrid().unwrap(), beatgrid);
+        assert_eq!(tag.serato_autotags().unwrap().auto_gain, -3.257);
+        assert!(tag.serato_overview().is_none());
+    }
+
+    #[test]
+    fn private_frames() {
+        use crate::{PrivateContent, PrivateFrame};
+        let mut tag = ID3TAG::new_empty_tag();
+        let media_class = PrivateContent::MediaClassPrimaryID("{D1607DBC-E323-4BE2-86A1-48A42A28441E}".into());
+        assert!(tag.add_private_frame(PrivateFrame::from_content(&media_class).unwrap()));
+        assert!(tag.add_private_frame(PrivateFrame::new("PeakValue", vec![0x10, 0x27, 0, 0])));
+        assert!(!tag.add_private_frame(PrivateFrame::new("PeakValue", vec![0x10, 0x27, 0, 0])));
+        tag.set_private_data("AverageLevel", vec![0xE8, 0x03, 0, 0]);
+        let mut bytes = tag.as_bytes();
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.private_data("WM/MediaClassPrimaryID")[0][0..4], [0xBC, 0x7D, 0x60, 0xD1]);
+        let contents = tag.private_frames().iter().filter_map(|frame| frame.content()).collect::<Vec<PrivateContent>>();
+        assert_eq!(contents, vec![media_class, PrivateContent::PeakValue(10_000), PrivateContent::AverageLevel(1000)]);
+        assert!(tag.remove_private_frames("PeakValue"));
+        assert_eq!(tag.private_frames().len(), 2);
+    }
+
+    #[test]
+    fn unique_file_identifiers() {
+        let mut tag = ID3TAG::new_empty_tag();
+        assert!(tag.set_unique_id("http://www.id3.org/dummy/ufid.html", &[1, 2, 3]));
+        assert!(!tag.set_unique_id("http://www.id3.org/dummy/ufid.html", &[0; 65]));
+        assert!(!tag.set_unique_id("", &[1]));
+        tag.set_musicbrainz_recording_id("0e4a4d8b-1c3f-4a50-9b3a-1f0a5e0c7b11");
+        tag.set_musicbrainz_recording_id("b1a9c0e9-d987-4042-ae91-78d6a3267d69");
+        let mut bytes = tag.as_bytes();
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.unique_ids().len(), 2);
+        assert_eq!(tag.unique_id("http://www.id3.org/dummy/ufid.html").unwrap(), &vec![1, 2, 3]);
+        assert_eq!(tag.musicbrainz_recording_id().unwrap(), "b1a9c0e9-d987-4042-ae91-78d6a3267d69");
+        assert!(tag.remove_unique_id("http://musicbrainz.org"));
+        assert!(tag.musicbrainz_recording_id().is_none());
+    }
+
+    #[test]
+    fn url_frames() {
+        use crate::ID3URLFRAMEID;
+        let mut tag = ID3TAG::new_empty_tag();
+        assert!(tag.add_url(ID3URLFRAMEID::WOAR, "https://artist.example.com"));
+        assert!(tag.add_url(ID3URLFRAMEID::WOAR, "https://feat.example.com"));
+        assert!(!tag.add_url(ID3URLFRAMEID::WOAR, "https://feat.example.com"));
+        tag.add_url(ID3URLFRAMEID::WPUB, "https://label.example.com");
+        tag.add_url(ID3URLFRAMEID::WPUB, "https://label.example.org");
+        tag.set_user_url("Podcast feed", "https://example.com/feed.xml");
+        tag.set_user_url("Épisode", "https://example.com/42");
+        let mut bytes = tag.as_bytes();
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.urls(ID3URLFRAMEID::WOAR), vec!["https://artist.example.com", "https://feat.example.com"]);
+        assert_eq!(tag.urls(ID3URLFRAMEID::WPUB), vec!["https://label.example.org"]);
+        assert_eq!(tag.user_url("Épisode").unwrap(), "https://example.com/42");
+        assert_eq!(tag.user_urls().len(), 2);
+        assert!(tag.remove_url(ID3URLFRAMEID::WOAR, "https://artist.example.com"));
+        assert_eq!(tag.url(ID3URLFRAMEID::WOAR).unwrap(), "https://feat.example.com");
+        assert!(tag.remove_user_url("Podcast feed"));
+    }
+
+    #[test]
+    fn user_texts() {
+        let mut tag = ID3TAG::new_empty_tag();
+        tag.set_user_text("CATALOGNUMBER", "WARPCD123");
+        tag.set_user_text("BARCODE", "5021603123456");
+        tag.set_user_text("Étiquette", "Warp");
+        assert!(tag.set_user_text("catalognumber", "WARPCD124"));
+        let mut bytes = tag.as_bytes();
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        let texts = tag.user_texts().collect::<Vec<(&str, &str)>>();
+        assert_eq!(texts, vec![("catalognumber", "WARPCD124"), ("BARCODE", "5021603123456"), ("Étiquette", "Warp")]);
+        assert_eq!(tag.get_user_text("ÉTIQUETTE").unwrap(), "Warp");
+        assert!(tag.remove_user_text("Barcode"));
+        assert!(!tag.remove_user_text("Barcode"));
+        assert_eq!(tag.user_texts().count(), 2);
+    }
+
+    #[test]
+    fn involved_people() {
+        let mut tag = ID3TAG::new_empty_tag();
+        tag.add_involved_person("producer", "Jane Doe");
+        tag.add_involved_person("engineer", "John Smith");
+        tag.add_involved_person("mixer", "Jane Doe");
+        assert!(!tag.add_involved_person("mixer", "Jane Doe"));
+        let mut bytes = tag.as_bytes();
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.involved_people().len(), 3);
+        assert_eq!(tag.involved_people_with_role("engineer"), vec!["John Smith".to_string()]);
+        tag.set_version(ID3Version::V2_4);
+        tag.add_musician_credit("cello", "Yo-Yo Ma");
+        assert!(tag.remove_involved_person("mixer", None));
+        let mut bytes = tag.as_bytes();
+        let mut tag = ID3TAG::new("", &mut bytes).unwrap();
+        let people = vec![("producer".to_string(), "Jane Doe".to_string()), ("engineer".to_string(), "John Smith".to_string())];
+        assert_eq!(tag.involved_people(), people);
+        assert_eq!(tag.musician_credits(), vec![("cello".to_string(), "Yo-Yo Ma".to_string())]);
+        tag.set_version(ID3Version::V2_3);
+        assert_eq!(tag.involved_people().len(), 3);
+        assert_eq!(tag.involved_people_with_role("cello"), vec!["Yo-Yo Ma".to_string()]);
+    }
+
+    #[test]
+    fn cd_toc_conversions() {
+        use crate::flac::flac_metadata_block::FlacMetadataBlockType;
+        use crate::flac::flac_metadata_block_data::{CueSheetBlock, FlacMetadataBlockData};
+        use crate::util::traits::RawSize;
+        use crate::{CdToc, CdTocTrack};
+        let toc = CdToc {
+            tracks: vec![
+                CdTocTrack { number: 1, offset: 0, is_audio: true },
+                CdTocTrack { number: 2, offset: 17_477, is_audio: true },
+                CdTocTrack { number: 3, offset: 35_206, is_audio: false },
+            ],
+            lead_out: 52_903,
+        };
+        assert_eq!(toc.track_sectors(2), Some(17_729));
+        let mut tag = ID3TAG::new_empty_tag();
+        tag.set_cd_toc(&toc);
+        let mut bytes = tag.as_bytes();
+        let tag = ID3TAG::new("", &mut bytes).unwrap();
+        assert_eq!(tag.cd_toc().unwrap(), toc);
+        let cue_sheet = CueSheetBlock::from_cd_toc(&tag.cd_toc().unwrap());
+        let mut bytes = cue_sheet.raw_bytes();
+        assert_eq!(bytes.len(), cue_sheet.raw_size());
+        let size = bytes.len() as u32;
+        let cue_sheet = FlacMetadataBlockData::new(&mut bytes, size, &FlacMetadataBlockType::CUESHEET).unwrap();
+        assert_eq!(cue_sheet.as_cue_sheet_block().unwrap().to_cd_toc().unwrap(), toc);
+    }
+
+    #[test]
+    fn linked_frames_and_position_sync() {
+        use crate::{LinkedInfoFrame, PositionSyncFrame, TimeStampFormat};
+        let directory = std::env::temp_dir().join("tag_edit_linked_frames");
+        std::fs::create_dir_all(&directory).unwrap();
+        let mut shared = ID3TAG::new_empty_tag();
+        shared.set_version(ID3Version::V2_4);
+        shared.add_picture(crate::PictureFormat::PNG, &vec![0x89, 0x50, 0x4E, 0x47], None, None);
+        shared.set_title("刹那");
+        std::fs::write(directory.join("cover.id3"), shared.as_bytes()).unwrap();
+
+        let mut tag = ID3TAG::new_empty_tag();
+        assert!(LinkedInfoFrame::new("apic", "cover.id3", "").is_none());
+        assert!(tag.add_linked_frame(LinkedInfoFrame::new("APIC", "cover.id3", "").unwrap()));
+        assert!(!tag.add_linked_frame(LinkedInfoFrame::new("APIC", "cover.id3", "").unwrap()));
+        tag.add_linked_frame(LinkedInfoFrame::new("COMM", "http://example.com/notes", "eng").unwrap());
+        tag.add_linked_frame(LinkedInfoFrame::new("TIT2", "cover.id3", "").unwrap());
+        tag.add_linked_frame(LinkedInfoFrame::new("TALB", "cover.id3", "").unwrap());
+        tag.set_position_sync(PositionSyncFrame { time_stamp_format: TimeStampFormat::Milliseconds, position: 93_000 });
+        let mut bytes = tag.as_bytes();
+        let path = directory.join("episode.mp3");
+        let mut tag = ID3TAG::new(path.to_str().unwrap(), &mut bytes).unwrap();
+        assert_eq!(tag.linked_frames()[1].id_data(), "eng");
+        assert_eq!(tag.position_sync().unwrap().position, 93_000);
+        assert_eq!(tag.resolve_linked_frames(), 2);
+        assert_eq!(tag.attached_pictures(), vec![&vec![0x89, 0x50, 0x4E, 0x47]]);
+        // The album isn't in the linked file, the link is kept
+        assert_eq!(tag.linked_frames().iter().map(|link| link.frame_id()).collect::<Vec<&str>>(), vec!["COMM", "TALB"]);
+        assert_eq!(tag.resolve_linked_frames(), 0);
+        // The UTF-8 title of the ID3v2.4 file is written in UTF-16
+        let bytes = tag.as_bytes();
+        let title = bytes.windows(4).position(|w| w == b"TIT2").unwrap();
+        assert_eq!(bytes[title + 10], 1);
+        assert_eq!(tag.title(), Some("刹那".to_string()));
+        assert!(tag.remove_linked_frames("COMM"));
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+
     #[test]
     fn flac_read() -> Result<(), Error>{
         if let Some(mut flactag) = FlacTag::from_path(FLAC_FILE) {
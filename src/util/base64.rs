@@ -0,0 +1,40 @@
+//! Base64 (RFC 4648) encoding used by the Serato objects
+
+const ALPHABET : &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Encode `bytes` in base64, without the `=` padding if `padding` is `false`
+pub(crate) fn encode(bytes : &[u8], padding : bool) -> String {
+    let mut text = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (index, byte)| group | (*byte as u32) << (16 - 8 * index));
+        for index in 0..=chunk.len() {
+            text.push(ALPHABET[((group >> (18 - 6 * index)) & 0x3F) as usize] as char)
+        }
+        if padding {
+            for _ in chunk.len()..3 {
+                text.push('=')
+            }
+        }
+    }
+    text
+}
+
+/// Decode base64 text, the whitespaces and the padding being ignored
+///
+/// A trailing lone character, which can't encode a byte, is ignored
+pub(crate) fn decode(text : &[u8]) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let mut group = 0u32;
+    let mut bits = 0;
+    for c in text.iter().filter(|c| !c.is_ascii_whitespace() && **c != b'=') {
+        let value = ALPHABET.iter().position(|a| a == c)? as u32;
+        group = (group << 6) | value;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((group >> bits) as u8);
+            group &= (1 << bits) - 1;
+        }
+    }
+    Some(bytes)
+}
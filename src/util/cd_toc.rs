@@ -0,0 +1,82 @@
+//! Table of contents of an audio CD, shared by the ID3 MCDI frame and the FLAC CUESHEET block
+
+use std::convert::TryInto;
+
+/// Number of sectors (frames) per second of an audio CD
+pub const SECTORS_PER_SECOND : u32 = 75;
+/// Number of samples of a sector at 44.1 kHz
+pub(crate) const SAMPLES_PER_SECTOR : u64 = 588;
+/// Track number of the lead-out
+pub(crate) const LEAD_OUT_TRACK : u8 = 0xAA;
+/// Q sub-channel ADR of the position data
+const ADR_POSITION : u8 = 0x10;
+/// Control bit of the data tracks
+const DATA_TRACK : u8 = 0x04;
+
+/// A track of a CD table of contents
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CdTocTrack {
+    /// Track number, from 1 to 99
+    pub number : u8,
+    /// Start of the track in sectors (1/75 s) from the beginning of the disc (logical block address)
+    pub offset : u32,
+    pub is_audio : bool
+}
+
+/// Table of contents of a CD
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct CdToc {
+    pub tracks : Vec<CdTocTrack>,
+    /// Start of the lead-out in sectors (1/75 s) from the beginning of the disc, i.e. the end of the last track
+    pub lead_out : u32
+}
+
+impl CdToc {
+    /// Returns the length of the track `number` in sectors
+    pub fn track_sectors(&self, number : u8) -> Option<u32> {
+        let index = self.tracks.iter().position(|track| track.number == number)?;
+        let end = self.tracks.get(index + 1).map_or(self.lead_out, |track| track.offset);
+        end.checked_sub(self.tracks[index].offset)
+    }
+
+    /// Parse a TOC as returned by the READ TOC command of a CD drive, with logical block addresses
+    pub(crate) fn from_mcdi(data : &[u8]) -> Option<Self> {
+        let length = u16::from_be_bytes(data.get(0..2)?.try_into().ok()?) as usize;
+        let descriptors = data.get(4..(2 + length))?;
+        let mut toc = Self::default();
+        let mut has_lead_out = false;
+        for descriptor in descriptors.chunks_exact(8) {
+            let offset = u32::from_be_bytes(descriptor[4..8].try_into().ok()?);
+            match descriptor[2] {
+                LEAD_OUT_TRACK => {
+                    toc.lead_out = offset;
+                    has_lead_out = true
+                }
+                number => toc.tracks.push(CdTocTrack { number, offset, is_audio: descriptor[1] & DATA_TRACK == 0 })
+            }
+        }
+        match has_lead_out && !toc.tracks.is_empty() {
+            true => Some(toc),
+            false => None
+        }
+    }
+
+    /// Format the TOC as returned by the READ TOC command of a CD drive, with logical block addresses
+    pub(crate) fn to_mcdi(&self) -> Vec<u8> {
+        let descriptor = |number : u8, offset : u32, is_audio : bool| {
+            let control = if is_audio { ADR_POSITION } else { ADR_POSITION | DATA_TRACK };
+            let mut bytes = vec![0, control, number, 0];
+            bytes.extend_from_slice(&offset.to_be_bytes());
+            bytes
+        };
+        let length = 2 + 8 * (self.tracks.len() + 1);
+        let mut bytes = (length as u16).to_be_bytes().to_vec();
+        bytes.push(self.tracks.first().map_or(1, |track| track.number));
+        bytes.push(self.tracks.last().map_or(1, |track| track.number));
+        for track in self.tracks.iter() {
+            bytes.append(&mut descriptor(track.number, track.offset, track.is_audio))
+        }
+        bytes.append(&mut descriptor(LEAD_OUT_TRACK, self.lead_out, true));
+        bytes
+    }
+}
@@ -0,0 +1,18 @@
+/// Reversed polynomial of the ISO-3309 CRC-32
+const CRC32_POLYNOMIAL : u32 = 0xEDB8_8320;
+
+/// CRC-32 as defined by ISO-3309 (the one used by zlib and the ID3v2 extended header)
+pub(crate) fn crc32(bytes : &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes.iter() {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 == 1 {
+                (crc >> 1) ^ CRC32_POLYNOMIAL
+            } else {
+                crc >> 1
+            }
+        }
+    }
+    !crc
+}
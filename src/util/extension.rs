@@ -142,12 +142,17 @@ impl SplitUF16 for Vec<u16> {
     fn split_to_string_utf16(&self) -> Vec<String>{
         let splits = 
         self.split(|n| *n == 0);
+        // Each string can begin with a byte order mark
         splits
         .into_iter()
+        .map(|s| match s.first() {
+            Some(0xFEFF) => String::from_utf16_lossy(&s[1..]),
+            Some(0xFFFE) => String::from_utf16_lossy(&s[1..].iter().map(|c| c.swap_bytes()).collect::<Vec<u16>>()),
+            _ => String::from_utf16_lossy(s)
+        })
         .filter(|s|  { 
         !s.is_empty()
     })
-        .map(|s| String::from_utf16_lossy(s.into()))
         .collect()
     }
 }
@@ -167,6 +172,8 @@ impl StringConvert for Vec<u8> {
     fn into_string(&self, encoding : &super::reading_mode::TextEncoding) -> Option<String> {
             match encoding {
                 TextEncoding::Iso8859_1 | TextEncoding::UnicodeUtf8 => self.to_utf8(),
+                TextEncoding::UnicodeUtf16 if self.starts_with(&[0xFE, 0xFF]) => self[2..].to_vec().to_utf16_be(),
+                TextEncoding::UnicodeUtf16 if self.starts_with(&[0xFF, 0xFE]) => self[2..].to_vec().to_uft16_le(),
                 TextEncoding::UnicodeUtf16 => self.to_uft16_le(),
                 TextEncoding::UnicodeBigEndian => self.to_utf16_be(),
             }
@@ -216,4 +223,25 @@ impl StringConvert for Vec<u8> {
         }
         string_vec.into_string(encoding)
     }
+
+    fn take_string(&mut self, encoding: &TextEncoding) -> Option<String> {
+        let terminator = if encoding.is_one_byte() {
+            self.iter().position(|byte| *byte == 0)
+        } else {
+            self.chunks_exact(2).position(|chunk| chunk == [0, 0]).map(|i| i * 2)
+        };
+        let (string_len, drain_len) = match terminator {
+            Some(index) => (index, index + encoding.encoding_size() as usize),
+            None => (self.len(), self.len())
+        };
+        let mut bytes = self.drain(0..drain_len).collect::<Vec<u8>>();
+        bytes.truncate(string_len);
+        match encoding {
+            TextEncoding::UnicodeUtf16 if bytes.starts_with(&[0xFE, 0xFF]) => bytes.split_off(2).to_utf16_be(),
+            TextEncoding::UnicodeUtf16 if bytes.starts_with(&[0xFF, 0xFE]) => bytes.split_off(2).to_uft16_le(),
+            // Fall back on ISO-8859-1 if the string isn't valid UTF-8
+            TextEncoding::Iso8859_1 => bytes.to_utf8().or_else(|| Some(bytes.iter().map(|byte| *byte as char).collect())),
+            _ => bytes.into_string(encoding)
+        }
+    }
 }
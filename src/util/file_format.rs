@@ -14,6 +14,14 @@ pub enum PictureFormat {
 }
 
 impl PictureFormat {
+    /// Picture format from the three characters image format of the ID3v2.2 PIC frame
+    pub (crate) fn from_image_format(image_format: &str) -> Self {
+        match image_format.to_uppercase().as_str() {
+            "JPG" | "JPEG" => PictureFormat::JPEG,
+            "PNG" => PictureFormat::PNG,
+            other => PictureFormat::OTHER(other.trim_end_matches('\u{0}').to_lowercase())
+        }
+    }
     pub (crate) fn to_mime_string(&self) -> String {
         match self {
             PictureFormat::PNG => "image/png".into(),
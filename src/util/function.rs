@@ -33,6 +33,38 @@ pub (crate) fn synchsafe(input : u32) -> u32 {
     }
     out
 }
+/// Apply the ID3 unsynchronisation scheme: a null byte is inserted after each 0xFF
+/// followed by a byte which could be mistaken for a MPEG frame sync (%111xxxxx) or by a null byte
+pub (crate) fn unsynchronise(bytes : &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len());
+    for (i, byte) in bytes.iter().enumerate() {
+        out.push(*byte);
+        if *byte == 0xFF {
+            match bytes.get(i + 1) {
+                Some(next) if *next == 0x00 || *next >= 0xE0 => out.push(0x00),
+                None => out.push(0x00),
+                _ => ()
+            }
+        }
+    }
+    out
+}
+
+/// Reverse the ID3 unsynchronisation scheme: each 0xFF 0x00 is replaced by 0xFF
+pub (crate) fn deunsynchronise(bytes : &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut previous_ff = false;
+    for byte in bytes.iter() {
+        if previous_ff && *byte == 0x00 {
+            previous_ff = false;
+            continue;
+        }
+        out.push(*byte);
+        previous_ff = *byte == 0xFF;
+    }
+    out
+}
+
 fn is_id3(s: &String) -> bool {
     s == "ID3"
 }
@@ -41,6 +73,7 @@ fn is_flac(s: &String) -> bool{
     s == "fLaC"
 }
 
+/// Returns the audio format and, for a mp3 file, the size of the ID3 tag (header included)
 pub (crate) fn read_type_audio_file(file: &mut File) -> Result<(AudioFormat, usize), FromUtf8Error> {
     let mut buffer = [0,0,0,0,0,0,0,0,0,0];
     let _ = file.read(&mut buffer);
@@ -48,15 +81,9 @@ pub (crate) fn read_type_audio_file(file: &mut File) -> Result<(AudioFormat, usi
     let id3 = String::from_utf8(buffer[0..3].to_vec())?;
     if is_id3(&id3) { 
         let flag = buffer[5];
-        let unsync_flag = ID3HeaderFLAG::Unsynchronisation as u8;
-        let size = if (flag & unsync_flag) != unsync_flag {
-            //println!("To unsynchsafe");
-            unsynchsafe(u32::from_be_bytes(buffer[6..].try_into().unwrap()))
-        }else {
-            //println!("unsynchsafe");
-            u32::from_be_bytes(buffer[6..].try_into().unwrap())
-        };
-        return Ok( (MP3, size as usize)); 
+        let size = unsynchsafe(u32::from_be_bytes(buffer[6..].try_into().unwrap()));
+        let footer_size = if buffer[3] == 4 && (flag & ID3HeaderFLAG::FooterPresent as u8) != 0 { 10 } else { 0 };
+        return Ok( (MP3, size as usize + 10 + footer_size)); 
     }
     if is_flac(&String::from_utf8(buffer[0..4].to_vec())?){ return Ok( (FLAC, 0) );  }
     Ok((OTHER, 0))
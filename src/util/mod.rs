@@ -5,3 +5,8 @@ pub (crate) mod reading_mode;
 pub (crate) mod extension;
 pub (crate) mod traits;
 pub (crate) mod vorbis_vector;
+pub (crate) mod crc;
+pub (crate) mod zlib;
+pub (crate) mod mpeg;
+pub (crate) mod base64;
+pub (crate) mod cd_toc;
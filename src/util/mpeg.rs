@@ -0,0 +1,93 @@
+/// Bitrates in kbit/s of MPEG-1 layer I, II and III
+const V1_BITRATES : [[u32; 14]; 3] = [
+    [32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448],
+    [32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384],
+    [32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320]
+];
+/// Bitrates in kbit/s of MPEG-2 and MPEG-2.5 layer I, and layers II and III
+const V2_BITRATES : [[u32; 14]; 2] = [
+    [32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256],
+    [8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160]
+];
+const V1_SAMPLE_RATES : [u32; 3] = [44100, 48000, 32000];
+
+/// Header of a MPEG audio frame
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct MpegFrameHeader {
+    /// Size of the frame in bytes, header included
+    pub(crate) size : usize,
+    pub(crate) samples : u32,
+    pub(crate) sample_rate : u32
+}
+
+impl MpegFrameHeader {
+    /// Parse the frame header at the beginning of `bytes`
+    ///
+    /// Free format frames aren't supported
+    pub(crate) fn from_bytes(bytes : &[u8]) -> Option<Self> {
+        if bytes.len() < 4 || bytes[0] != 0xFF || bytes[1] & 0xE0 != 0xE0 {
+            return None;
+        }
+        // 0 : MPEG-2.5, 2 : MPEG-2, 3 : MPEG-1
+        let version = (bytes[1] >> 3) & 0b11;
+        // 1 : layer III, 2 : layer II, 3 : layer I
+        let layer = (bytes[1] >> 1) & 0b11;
+        let bitrate_index = (bytes[2] >> 4) as usize;
+        let sample_rate_index = ((bytes[2] >> 2) & 0b11) as usize;
+        let padding = ((bytes[2] >> 1) & 1) as u32;
+        if version == 1 || layer == 0 || bitrate_index == 0 || bitrate_index == 15 || sample_rate_index == 3 {
+            return None;
+        }
+        let bitrate = match (version, layer) {
+            (3, layer) => V1_BITRATES[(3 - layer) as usize][bitrate_index - 1],
+            (_, 3) => V2_BITRATES[0][bitrate_index - 1],
+            _ => V2_BITRATES[1][bitrate_index - 1]
+        } * 1000;
+        // Halved in MPEG-2, quartered in MPEG-2.5
+        let sample_rate = V1_SAMPLE_RATES[sample_rate_index] >> (3 - version.max(1));
+        let samples = match (version, layer) {
+            (_, 3) => 384,
+            (3, _) | (_, 2) => 1152,
+            _ => 576
+        };
+        let size = if layer == 3 {
+            (12 * bitrate / sample_rate + padding) * 4
+        } else {
+            samples / 8 * bitrate / sample_rate + padding
+        };
+        Some(Self { size: size as usize, samples, sample_rate })
+    }
+
+    /// Duration of the frame in milliseconds
+    pub(crate) fn duration(&self) -> f64 {
+        self.samples as f64 * 1000.0 / self.sample_rate as f64
+    }
+}
+
+/// Returns the offset and the header of the MPEG frames of `data`
+///
+/// A frame is only accepted if it is followed by another frame or by the end of the data,
+/// garbage between the frames is skipped
+pub(crate) fn mpeg_frames(data : &[u8]) -> Vec<(usize, MpegFrameHeader)> {
+    let mut frames = vec![];
+    let mut offset = 0;
+    while offset + 4 <= data.len() {
+        let header = match MpegFrameHeader::from_bytes(&data[offset..]) {
+            Some(header) => header,
+            None => {
+                offset += 1;
+                continue;
+            }
+        };
+        let next = offset + header.size;
+        let is_followed = next >= data.len() || MpegFrameHeader::from_bytes(&data[next..]).is_some();
+        // The first frame found must be followed by a frame to avoid a false synchronisation
+        if next > data.len() || (!is_followed && frames.is_empty()) {
+            offset += 1;
+            continue;
+        }
+        frames.push((offset, header));
+        offset = next;
+    }
+    frames
+}
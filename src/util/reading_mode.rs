@@ -25,6 +25,13 @@ impl TextEncoding {
             TextEncoding::UnicodeUtf16 | TextEncoding::UnicodeBigEndian => 2,
         }
     }
+    /// Returns the closest encoding allowed by the ID3v2.`version` specification
+    pub(crate) fn for_version(&self, version : u8) -> TextEncoding {
+        match self {
+            TextEncoding::UnicodeUtf8 | TextEncoding::UnicodeBigEndian if version == 3 => TextEncoding::UnicodeUtf16,
+            _ => *self
+        }
+    }
     pub fn is_one_byte(&self) -> bool {
         match self {
             TextEncoding::Iso8859_1 | TextEncoding::UnicodeUtf8 => true,
@@ -36,6 +36,10 @@ pub (crate) trait StringConvert {
     fn to_uft16_le(&self) -> Option<String>;
     fn to_utf16_be(&self) -> Option<String>;
     fn first_matched_string(&mut self, encoding: &TextEncoding, drain : bool) -> Option<String>;
+    /// Remove the first null terminated string from the buffer and returns it without the terminator
+    ///
+    /// The terminator of the UTF-16 strings is searched on a 2 bytes boundary and the byte order mark is honoured
+    fn take_string(&mut self, encoding: &TextEncoding) -> Option<String>;
 }
 
 pub(crate) trait SliceConvert {
@@ -0,0 +1,391 @@
+//! Minimal zlib (RFC 1950) and deflate (RFC 1951) implementation used by the compressed ID3 frames
+
+/// zlib header : deflate with a 32K window, default compression level
+const ZLIB_HEADER : [u8; 2] = [0x78, 0x9C];
+/// Preset dictionary bit of the zlib header
+const FDICT : u8 = 0b00_100_000;
+
+const MAX_BITS : usize = 15;
+const WINDOW_SIZE : usize = 32_768;
+const MIN_MATCH : usize = 3;
+const MAX_MATCH : usize = 258;
+const MAX_CHAIN : usize = 64;
+const HASH_SIZE : usize = 1 << 15;
+const MAX_STORED_BLOCK : usize = 65_535;
+
+const LENGTH_BASE : [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31,
+    35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258
+];
+const LENGTH_EXTRA : [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2,
+    3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0
+];
+const DISTANCE_BASE : [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193,
+    257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577
+];
+const DISTANCE_EXTRA : [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6,
+    7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13
+];
+/// Order of the code length code lengths in a dynamic block
+const CODE_LENGTH_ORDER : [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// Adler-32 checksum ending a zlib stream
+pub(crate) fn adler32(bytes : &[u8]) -> u32 {
+    const MOD_ADLER : u32 = 65_521;
+    let mut a = 1u32;
+    let mut b = 0u32;
+    for chunk in bytes.chunks(5552) {
+        for byte in chunk {
+            a += *byte as u32;
+            b += a;
+        }
+        a %= MOD_ADLER;
+        b %= MOD_ADLER;
+    }
+    (b << 16) | a
+}
+
+/// Decompress a zlib stream into at most `limit` bytes
+///
+/// Returns `None` if the stream is malformed, its checksum doesn't match or it expands past `limit`
+pub(crate) fn inflate(bytes : &[u8], limit : usize) -> Option<Vec<u8>> {
+    if bytes.len() < 6 || bytes[0] & 0x0F != 8 || !u16::from_be_bytes([bytes[0], bytes[1]]).is_multiple_of(31) || bytes[1] & FDICT != 0 {
+        return None;
+    }
+    let mut reader = BitReader { bytes: &bytes[2..], position: 0, bit_buffer: 0, bit_count: 0, limit };
+    let out = reader.inflate()?;
+    let checksum = bytes.get((2 + reader.position)..(6 + reader.position))?;
+    if u32::from_be_bytes([checksum[0], checksum[1], checksum[2], checksum[3]]) != adler32(&out) {
+        return None;
+    }
+    Some(out)
+}
+
+/// Compress `bytes` into a zlib stream
+pub(crate) fn deflate(bytes : &[u8]) -> Vec<u8> {
+    let mut out = ZLIB_HEADER.to_vec();
+    let compressed = deflate_fixed(bytes);
+    let stored_size = bytes.len() + 5 * (bytes.len() / MAX_STORED_BLOCK + 1);
+    if compressed.len() < stored_size {
+        out.extend(compressed);
+    } else {
+        out.extend(deflate_stored(bytes));
+    }
+    out.extend_from_slice(&adler32(bytes).to_be_bytes());
+    out
+}
+
+/// Canonical Huffman code
+struct Huffman {
+    /// Number of codes of each length
+    counts : [u16; MAX_BITS + 1],
+    /// Symbols ordered by code
+    symbols : Vec<u16>
+}
+
+impl Huffman {
+    fn new(lengths : &[u8]) -> Self {
+        let mut counts = [0u16; MAX_BITS + 1];
+        for length in lengths.iter() {
+            counts[*length as usize] += 1;
+        }
+        let mut offsets = [0u16; MAX_BITS + 2];
+        for length in 1..=MAX_BITS {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, length) in lengths.iter().enumerate() {
+            if *length != 0 {
+                symbols[offsets[*length as usize] as usize] = symbol as u16;
+                offsets[*length as usize] += 1;
+            }
+        }
+        Self { counts, symbols }
+    }
+
+    fn fixed() -> (Self, Self) {
+        let mut lengths = [0u8; 288];
+        lengths[0..144].iter_mut().for_each(|length| *length = 8);
+        lengths[144..256].iter_mut().for_each(|length| *length = 9);
+        lengths[256..280].iter_mut().for_each(|length| *length = 7);
+        lengths[280..288].iter_mut().for_each(|length| *length = 8);
+        (Self::new(&lengths), Self::new(&[5u8; 30]))
+    }
+}
+
+struct BitReader<'a> {
+    bytes : &'a [u8],
+    /// Index of the next byte to load
+    position : usize,
+    bit_buffer : u32,
+    bit_count : u32,
+    /// Maximum size of the decompressed data
+    limit : usize
+}
+
+impl BitReader<'_> {
+    fn bits(&mut self, count : u32) -> Option<u32> {
+        while self.bit_count < count {
+            let byte = *self.bytes.get(self.position)? as u32;
+            self.position += 1;
+            self.bit_buffer |= byte << self.bit_count;
+            self.bit_count += 8;
+        }
+        let value = self.bit_buffer & ((1u32 << count) - 1);
+        self.bit_buffer >>= count;
+        self.bit_count -= count;
+        Some(value)
+    }
+
+    /// Drop the remaining bits of the current byte
+    fn align(&mut self) {
+        self.bit_buffer = 0;
+        self.bit_count = 0;
+    }
+
+    fn decode(&mut self, huffman : &Huffman) -> Option<u16> {
+        let mut code = 0i32;
+        let mut first = 0i32;
+        let mut index = 0i32;
+        for length in 1..=MAX_BITS {
+            code |= self.bits(1)? as i32;
+            let count = huffman.counts[length] as i32;
+            if code - first < count {
+                return huffman.symbols.get((index + code - first) as usize).copied();
+            }
+            index += count;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+        None
+    }
+
+    fn inflate(&mut self) -> Option<Vec<u8>> {
+        let mut out = vec![];
+        loop {
+            let last = self.bits(1)? == 1;
+            match self.bits(2)? {
+                0 => self.stored(&mut out)?,
+                1 => {
+                    let (literals, distances) = Huffman::fixed();
+                    self.codes(&mut out, &literals, &distances)?
+                }
+                2 => {
+                    let (literals, distances) = self.dynamic_tables()?;
+                    self.codes(&mut out, &literals, &distances)?
+                }
+                _ => return None
+            }
+            if last {
+                return Some(out);
+            }
+        }
+    }
+
+    fn stored(&mut self, out : &mut Vec<u8>) -> Option<()> {
+        self.align();
+        let header = self.bytes.get(self.position..(self.position + 4))?;
+        let length = u16::from_le_bytes([header[0], header[1]]);
+        if length != !u16::from_le_bytes([header[2], header[3]]) {
+            return None;
+        }
+        self.position += 4;
+        if out.len() + length as usize > self.limit {
+            return None;
+        }
+        out.extend_from_slice(self.bytes.get(self.position..(self.position + length as usize))?);
+        self.position += length as usize;
+        Some(())
+    }
+
+    fn dynamic_tables(&mut self) -> Option<(Huffman, Huffman)> {
+        let literal_count = self.bits(5)? as usize + 257;
+        let distance_count = self.bits(5)? as usize + 1;
+        let code_length_count = self.bits(4)? as usize + 4;
+        if literal_count > 286 || distance_count > 30 {
+            return None;
+        }
+        let mut code_lengths = [0u8; 19];
+        for index in CODE_LENGTH_ORDER.iter().take(code_length_count) {
+            code_lengths[*index] = self.bits(3)? as u8;
+        }
+        let code_length_huffman = Huffman::new(&code_lengths);
+        let mut lengths = Vec::with_capacity(literal_count + distance_count);
+        while lengths.len() < literal_count + distance_count {
+            let (length, repeat) = match self.decode(&code_length_huffman)? {
+                symbol @ 0..=15 => (symbol as u8, 1),
+                16 => (*lengths.last()?, 3 + self.bits(2)?),
+                17 => (0, 3 + self.bits(3)?),
+                18 => (0, 11 + self.bits(7)?),
+                _ => return None
+            };
+            lengths.extend(std::iter::repeat_n(length, repeat as usize));
+        }
+        if lengths.len() != literal_count + distance_count {
+            return None;
+        }
+        let (literal_lengths, distance_lengths) = lengths.split_at(literal_count);
+        Some((Huffman::new(literal_lengths), Huffman::new(distance_lengths)))
+    }
+
+    fn codes(&mut self, out : &mut Vec<u8>, literals : &Huffman, distances : &Huffman) -> Option<()> {
+        loop {
+            let symbol = self.decode(literals)? as usize;
+            match symbol {
+                0..=255 => {
+                    if out.len() == self.limit {
+                        return None;
+                    }
+                    out.push(symbol as u8)
+                }
+                256 => return Some(()),
+                _ => {
+                    let index = symbol - 257;
+                    let length = *LENGTH_BASE.get(index)? as usize + self.bits(LENGTH_EXTRA[index] as u32)? as usize;
+                    let index = self.decode(distances)? as usize;
+                    let distance = *DISTANCE_BASE.get(index)? as usize + self.bits(DISTANCE_EXTRA[index] as u32)? as usize;
+                    if distance > out.len() || out.len() + length > self.limit {
+                        return None;
+                    }
+                    let start = out.len() - distance;
+                    // The match can overlap the bytes it produces
+                    for i in 0..length {
+                        out.push(out[start + i]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct BitWriter {
+    bytes : Vec<u8>,
+    bit_buffer : u32,
+    bit_count : u32
+}
+
+impl BitWriter {
+    fn bits(&mut self, value : u32, count : u32) {
+        self.bit_buffer |= value << self.bit_count;
+        self.bit_count += count;
+        while self.bit_count >= 8 {
+            self.bytes.push(self.bit_buffer as u8);
+            self.bit_buffer >>= 8;
+            self.bit_count -= 8;
+        }
+    }
+
+    /// Write a Huffman code, most significant bit first
+    fn code(&mut self, code : u32, length : u32) {
+        let reversed = code.reverse_bits() >> (32 - length);
+        self.bits(reversed, length)
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.bit_count > 0 {
+            self.bytes.push(self.bit_buffer as u8);
+        }
+        self.bytes
+    }
+
+    fn literal(&mut self, symbol : u16) {
+        match symbol {
+            0..=143 => self.code(0x30 + symbol as u32, 8),
+            144..=255 => self.code(0x190 + (symbol as u32 - 144), 9),
+            256..=279 => self.code(symbol as u32 - 256, 7),
+            _ => self.code(0xC0 + (symbol as u32 - 280), 8)
+        }
+    }
+
+    fn length_distance(&mut self, length : usize, distance : usize) {
+        let index = LENGTH_BASE.iter().rposition(|base| *base as usize <= length).unwrap_or(0);
+        self.literal(257 + index as u16);
+        self.bits((length - LENGTH_BASE[index] as usize) as u32, LENGTH_EXTRA[index] as u32);
+        let index = DISTANCE_BASE.iter().rposition(|base| *base as usize <= distance).unwrap_or(0);
+        self.code(index as u32, 5);
+        self.bits((distance - DISTANCE_BASE[index] as usize) as u32, DISTANCE_EXTRA[index] as u32);
+    }
+}
+
+fn hash(bytes : &[u8]) -> usize {
+    ((bytes[0] as usize) << 10 ^ (bytes[1] as usize) << 5 ^ bytes[2] as usize) & (HASH_SIZE - 1)
+}
+
+/// Chain `position` to the previous positions starting with the same 3 bytes
+fn insert_hash(bytes : &[u8], position : usize, head : &mut [usize], previous : &mut [usize]) {
+    if position + MIN_MATCH <= bytes.len() {
+        let h = hash(&bytes[position..]);
+        previous[position] = head[h];
+        head[h] = position;
+    }
+}
+
+/// A single block compressed with the fixed Huffman codes
+fn deflate_fixed(bytes : &[u8]) -> Vec<u8> {
+    let mut writer = BitWriter::default();
+    writer.bits(1, 1);
+    writer.bits(1, 2);
+    let mut head = vec![usize::MAX; HASH_SIZE];
+    let mut previous = vec![usize::MAX; bytes.len()];
+    let mut position = 0;
+    while position < bytes.len() {
+        let mut best_length = 0;
+        let mut best_distance = 0;
+        if position + MIN_MATCH <= bytes.len() {
+            let mut candidate = head[hash(&bytes[position..])];
+            let mut chain = 0;
+            let max_length = MAX_MATCH.min(bytes.len() - position);
+            while candidate != usize::MAX && position - candidate <= WINDOW_SIZE && chain < MAX_CHAIN {
+                let length = bytes[candidate..]
+                    .iter()
+                    .zip(bytes[position..(position + max_length)].iter())
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                if length > best_length {
+                    best_length = length;
+                    best_distance = position - candidate;
+                    if length == max_length {
+                        break;
+                    }
+                }
+                candidate = previous[candidate];
+                chain += 1;
+            }
+        }
+        if best_length >= MIN_MATCH {
+            writer.length_distance(best_length, best_distance);
+            for i in position..(position + best_length) {
+                insert_hash(bytes, i, &mut head, &mut previous);
+            }
+            position += best_length;
+        } else {
+            writer.literal(bytes[position] as u16);
+            insert_hash(bytes, position, &mut head, &mut previous);
+            position += 1;
+        }
+    }
+    writer.literal(256);
+    writer.finish()
+}
+
+/// Blocks of uncompressed data
+fn deflate_stored(bytes : &[u8]) -> Vec<u8> {
+    let mut out = vec![];
+    let mut chunks = bytes.chunks(MAX_STORED_BLOCK).peekable();
+    if chunks.peek().is_none() {
+        out.push(1);
+        out.extend_from_slice(&[0x00, 0x00, 0xFF, 0xFF]);
+    }
+    while let Some(chunk) = chunks.next() {
+        out.push(if chunks.peek().is_none() { 1 } else { 0 });
+        out.extend_from_slice(&(chunk.len() as u16).to_le_bytes());
+        out.extend_from_slice(&(!(chunk.len() as u16)).to_le_bytes());
+        out.extend_from_slice(chunk);
+    }
+    out
+}